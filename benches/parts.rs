@@ -0,0 +1,49 @@
+/*
+ * Times every part of every day against its example, so that a change which slows a day down
+ * shows up. Run it with `cargo bench`, optionally naming the days to time: `cargo bench -- 12 17`.
+ *
+ * This would usually be a Criterion benchmark, but that isn't a dependency of the crate, so it
+ * uses the same loop as advent_of_code::bench instead.
+ */
+use std::time::Duration;
+
+use advent_of_code::{bench_with, read_example, solutions};
+
+// Roughly how long to spend timing each part. Quick parts are run many times to get a steadier
+// mean; slow ones only as often as fits.
+const BUDGET: Duration = Duration::from_millis(500);
+const MAX_ITERATIONS: usize = 1000;
+
+fn main() {
+    // cargo passes --bench, and possibly other flags, alongside any days asked for
+    let days: Vec<u8> = std::env::args()
+        .skip(1)
+        .filter(|arg| !arg.starts_with('-'))
+        .map(|arg| arg.parse().expect("days to benchmark must be numbers"))
+        .collect();
+
+    for (day, _) in solutions::solutions() {
+        if !days.is_empty() && !days.contains(&day) {
+            continue;
+        }
+
+        let input = read_example(day).unwrap_or_else(|err| panic!("{}", err));
+
+        for part in [1, 2] {
+            let solver = solutions::part(day, part).unwrap();
+
+            // one run to warm up and see how long a run takes
+            let first = bench_with(solver, &input, 1);
+            let iterations = (BUDGET.as_nanos() / first.as_nanos().max(1))
+                .clamp(1, MAX_ITERATIONS as u128) as usize;
+
+            println!(
+                "day {:02} part {}: {:>12.2?} (mean of {} runs)",
+                day,
+                part,
+                bench_with(solver, &input, iterations),
+                iterations
+            );
+        }
+    }
+}
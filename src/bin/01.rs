@@ -1,38 +1,63 @@
-/// Nice easy one to start off, summing some groups and chunking where needed. Nothing really to
-/// report.
+//! Nice easy one to start off, summing some groups and chunking where needed. Nothing really to
+//! report.
+
+use advent_of_code::helpers::top_n;
 
 fn parse(input: &str) -> Result<Vec<u32>, Box<dyn std::error::Error>> {
     let mut elves: Vec<Vec<u32>> = vec![vec![]];
 
-    for line in input.lines() {
+    // lines() already drops the \r of a \r\n, but trim anyway in case of stray whitespace
+    for line in input.lines().map(str::trim) {
         if line.is_empty() {
-            elves.push(vec![]);
+            // several blank lines in a row don't make for extra empty elves
+            if !elves.last().unwrap().is_empty() {
+                elves.push(vec![]);
+            }
             continue;
         }
 
         elves.last_mut().unwrap().push(line.parse()?);
     }
 
+    // nor do blank lines at the end, though there's always at least one elf
+    if elves.len() > 1 && elves.last().unwrap().is_empty() {
+        elves.pop();
+    }
+
     let sums: Vec<u32> = elves.iter().map(|elf| elf.iter().sum::<u32>()).collect();
 
     Ok(sums)
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+// Which elf is carrying the most, counting from 1, and how much they have. The first elf wins a tie.
+// There's always at least one elf, even if they carry nothing.
+pub fn max_elf(input: &str) -> (usize, u32) {
     let elves = parse(input).unwrap();
 
-    Some(*elves.iter().max().unwrap() as u32)
+    elves
+        .iter()
+        .enumerate()
+        .fold((1, 0), |(best, most), (i, &calories)| {
+            if calories > most {
+                (i + 1, calories)
+            } else {
+                (best, most)
+            }
+        })
+}
+
+pub fn part_one(input: &str) -> Option<u32> {
+    Some(max_elf(input).1)
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let mut elves = parse(input).unwrap();
-    elves.sort_unstable();
+    let elves = parse(input).unwrap();
 
-    Some(elves[elves.len() - 3..elves.len()].iter().sum())
+    Some(top_n(&elves, 3))
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 1);
+    let input = &advent_of_code::read_input_or_stdin(1);
 
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
@@ -44,13 +69,48 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 1);
+        let input = advent_of_code::read_example(1).unwrap();
         assert_eq!(part_one(&input), Some(24_000));
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 1);
+        let input = advent_of_code::read_example(1).unwrap();
         assert_eq!(part_two(&input), Some(45_000));
     }
+
+    #[test]
+    fn test_max_elf() {
+        let input = advent_of_code::read_example(1).unwrap();
+        assert_eq!(max_elf(&input), (4, 24_000));
+
+        // the first of the biggest elves wins
+        assert_eq!(max_elf("100\n\n300\n\n200\n100"), (2, 300));
+
+        // nobody carrying anything still leaves somebody at the front
+        assert_eq!(max_elf(""), (1, 0));
+        assert_eq!(max_elf("\n\n"), (1, 0));
+    }
+
+    #[test]
+    fn test_line_endings_and_blank_lines() {
+        let input = advent_of_code::read_example(1).unwrap();
+        let crlf = input.replace('\n', "\r\n");
+        let padded = format!("{}\n\n\n", input.replace("\n\n", "\n\n \n\n"));
+
+        assert_eq!(parse(&crlf).unwrap(), parse(&input).unwrap());
+        assert_eq!(parse(&padded).unwrap(), parse(&input).unwrap());
+
+        for input in [crlf, padded] {
+            assert_eq!(part_one(&input), Some(24_000));
+            assert_eq!(part_two(&input), Some(45_000));
+        }
+    }
+
+    #[test]
+    fn test_part_two_unsorted() {
+        // the biggest elves come first and last, with a smaller one between them
+        let input = "9000\n\n100\n\n1000\n1000\n\n50\n\n8000";
+        assert_eq!(part_two(input), Some(19_000));
+    }
 }
@@ -4,7 +4,7 @@
 /// idiomatic code, so I'm fine with it.
 use std::{error::Error, fmt::Display, str::FromStr};
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 enum Move {
     Rock,
     Paper,
@@ -16,7 +16,7 @@ struct ParseMoveError {}
 
 impl Error for ParseMoveError {}
 
-const PARSE_MOVE_ERROR: &'static str = "invalid move key provided";
+const PARSE_MOVE_ERROR: &str = "invalid move key provided";
 
 impl Display for ParseMoveError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -58,36 +58,36 @@ impl Move {
         }
     }
 
+    // Each move beats the one before it, wrapping around, so numbering them makes the outcome of a
+    // round a matter of arithmetic.
+    fn index(&self) -> usize {
+        match self {
+            Self::Rock => 0,
+            Self::Paper => 1,
+            Self::Scissors => 2,
+        }
+    }
+
+    fn from_index(i: usize) -> Self {
+        MOVES[i % 3]
+    }
+
+    // The move this one beats.
+    pub fn beats(&self) -> Move {
+        Self::from_index(self.index() + 2)
+    }
+
+    // The move that beats this one.
+    pub fn loses_to(&self) -> Move {
+        Self::from_index(self.index() + 1)
+    }
+
     pub fn outcome_with(&self, other: &Self) -> Outcome {
-        if *other == *self {
-            Outcome::Draw
-        } else {
-            // Rock beats Scissors
-            // Paper beats Rock
-            // Scissors beats Paper
-            match self {
-                Self::Rock => {
-                    if *other == Self::Scissors {
-                        Outcome::Win
-                    } else {
-                        Outcome::Loss
-                    }
-                }
-                Self::Paper => {
-                    if *other == Self::Rock {
-                        Outcome::Win
-                    } else {
-                        Outcome::Loss
-                    }
-                }
-                Self::Scissors => {
-                    if *other == Self::Paper {
-                        Outcome::Win
-                    } else {
-                        Outcome::Loss
-                    }
-                }
-            }
+        // how many steps around from their move to ours
+        match (self.index() + 3 - other.index()) % 3 {
+            0 => Outcome::Draw,
+            1 => Outcome::Win,
+            _ => Outcome::Loss,
         }
     }
 }
@@ -104,7 +104,7 @@ struct ParseOutcomeError {}
 
 impl Error for ParseOutcomeError {}
 
-const PARSE_OUTCOME_ERROR: &'static str = "invalid outcome key provided";
+const PARSE_OUTCOME_ERROR: &str = "invalid outcome key provided";
 
 impl Display for ParseOutcomeError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -158,60 +158,50 @@ const MOVES: [Move; 3] = [Move::Rock, Move::Paper, Move::Scissors];
 
 impl Round2 {
     pub fn our_move(&self) -> Move {
-        for m in MOVES {
-            if m.outcome_with(&self.them) == self.desired_outcome {
-                // dbg!(&self.them, &m, &self.desired_outcome);
-                return m;
-            }
+        match self.desired_outcome {
+            Outcome::Loss => self.them.beats(),
+            Outcome::Draw => self.them,
+            Outcome::Win => self.them.loses_to(),
         }
-
-        panic!("unable to find desired move");
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let mut rounds: Vec<Round> = vec![];
-
-    for line in input.lines() {
-        let (them, us) = line.split_once(' ').unwrap();
-
-        rounds.push(Round {
-            them: them.parse().unwrap(),
-            us: us.parse().unwrap(),
-        });
-    }
-
-    let outcome: u32 = rounds
-        .iter()
-        .map(|round| round.us.score() as u32 + round.outcome().score())
-        .sum();
+// Both parts' scores from one pass over the rounds. The two parts only disagree about what the
+// second key means, so each line is split once and its key read both ways.
+pub fn scores(input: &str) -> (u32, u32) {
+    input
+        .lines()
+        .map(|line| {
+            let (them, key) = line.split_once(' ').unwrap();
+
+            let round = Round {
+                them: them.parse().unwrap(),
+                us: key.parse().unwrap(),
+            };
+            // in part two the second key is actually the desired outcome
+            let round2 = Round2 {
+                them: them.parse().unwrap(),
+                desired_outcome: key.parse().unwrap(),
+            };
+
+            (
+                round.us.score() as u32 + round.outcome().score(),
+                round2.our_move().score() as u32 + round2.desired_outcome.score(),
+            )
+        })
+        .fold((0, 0), |(one, two), (a, b)| (one + a, two + b))
+}
 
-    Some(outcome)
+pub fn part_one(input: &str) -> Option<u32> {
+    Some(scores(input).0)
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    // change the parsing logic so the second key is actually the desired outcome
-    let mut rounds: Vec<Round2> = vec![];
-
-    for line in input.lines() {
-        let (them, desired_outcome) = line.split_once(' ').unwrap();
-
-        rounds.push(Round2 {
-            them: them.parse().unwrap(),
-            desired_outcome: desired_outcome.parse().unwrap(),
-        });
-    }
-
-    let score: u32 = rounds
-        .iter()
-        .map(|round| round.our_move().score() as u32 + round.desired_outcome.score())
-        .sum();
-
-    Some(score)
+    Some(scores(input).1)
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 2);
+    let input = &advent_of_code::read_input_or_stdin(2);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
@@ -222,13 +212,73 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 2);
+        let input = advent_of_code::read_example(2).unwrap();
         assert_eq!(part_one(&input), Some(15));
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 2);
+        let input = advent_of_code::read_example(2).unwrap();
         assert_eq!(part_two(&input), Some(12));
     }
+
+    #[test]
+    fn test_beats_and_loses_to() {
+        use Move::*;
+
+        assert_eq!(Rock.beats(), Scissors);
+        assert_eq!(Paper.beats(), Rock);
+        assert_eq!(Scissors.beats(), Paper);
+
+        assert_eq!(Rock.loses_to(), Paper);
+        assert_eq!(Paper.loses_to(), Scissors);
+        assert_eq!(Scissors.loses_to(), Rock);
+
+        for m in MOVES {
+            assert_eq!(m.outcome_with(&m.beats()), Outcome::Win);
+            assert_eq!(m.outcome_with(&m.loses_to()), Outcome::Loss);
+        }
+    }
+
+    #[test]
+    fn test_outcomes() {
+        use Move::*;
+        use Outcome::*;
+
+        for (us, them, outcome) in [
+            (Rock, Rock, Draw),
+            (Rock, Paper, Loss),
+            (Rock, Scissors, Win),
+            (Paper, Rock, Win),
+            (Paper, Paper, Draw),
+            (Paper, Scissors, Loss),
+            (Scissors, Rock, Loss),
+            (Scissors, Paper, Win),
+            (Scissors, Scissors, Draw),
+        ] {
+            assert_eq!(us.outcome_with(&them), outcome, "{:?} vs {:?}", us, them);
+
+            let round = Round2 {
+                them,
+                desired_outcome: outcome,
+            };
+            assert_eq!(
+                round.our_move(),
+                us,
+                "{:?} against {:?}",
+                round.desired_outcome,
+                them
+            );
+        }
+    }
+
+    #[test]
+    fn test_scores() {
+        let input = advent_of_code::read_example(2).unwrap();
+        assert_eq!(scores(&input), (15, 12));
+
+        // a line on its own: scissors against scissors in part one, and a win over them in two
+        assert_eq!(scores("C Z"), (3 + 3, 1 + 6));
+        assert_eq!(scores(""), (0, 0));
+    }
 }
@@ -1,7 +1,8 @@
-/// This one was good fun and some reasonable assumptions can be made based on an even number of
-/// items per bag. Just need to watch out for exclusive top slice indices which caught me out
-/// briefly and wasn't caught in the example input.
-use std::collections::{HashMap, HashSet};
+//! This one was good fun and some reasonable assumptions can be made based on an even number of
+//! items per bag. Just need to watch out for exclusive top slice indices which caught me out
+//! briefly and wasn't caught in the example input.
+
+use std::fmt::Display;
 
 fn parse(input: &str) -> Vec<String> {
     let sacks: Vec<String> = input.lines().map(|s| s.to_string()).collect();
@@ -9,100 +10,111 @@ fn parse(input: &str) -> Vec<String> {
     sacks
 }
 
-fn priority(ch: char) -> u8 {
-    if ch.is_uppercase() {
-        ch as u8 - 'A' as u8 + 27
-    } else if ch.is_lowercase() {
-        ch as u8 - 'a' as u8 + 1
+// The priority of an item, or the character back if it isn't one.
+fn priority(ch: char) -> Result<u8, char> {
+    if ch.is_ascii_uppercase() {
+        Ok(ch as u8 - b'A' + 27)
+    } else if ch.is_ascii_lowercase() {
+        Ok(ch as u8 - b'a' + 1)
     } else {
-        panic!("not a suitable item key")
+        Err(ch)
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let sacks = parse(input);
-
-    let priorities = sacks.iter().map(|sack| {
-        // we can assume the string is of even length, but check
-        assert!(sack.len() % 2 == 0, "expected sack to be of even item size");
-        assert!(sack.len() > 0, "sack contains no items");
-
-        let sack = sack.chars().collect::<Vec<char>>();
-
-        let (mut xs, mut ys) = (
-            sack[0..sack.len() / 2].to_vec(),
-            sack[sack.len() / 2..].to_vec(),
-        );
-
-        xs.sort();
-        ys.sort();
-
-        let mut i = 0;
-        let mut j = 0;
-
-        dbg!(sack, &xs, &ys);
-
-        loop {
-            let (x, y) = (xs[i] as u32, ys[j] as u32);
-
-            assert!(i < xs.len());
-            assert!(j < ys.len());
+// A character in a sack which isn't an item, and which line of the input it's on, counting from 1.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ItemError {
+    item: char,
+    line: usize,
+}
 
-            if x < y {
-                i += 1;
-            } else if x > y {
-                j += 1;
-            } else {
-                // x == y
-                break;
-            }
-        }
+impl Display for ItemError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} on line {} is not an item", self.item, self.line)
+    }
+}
 
-        priority(xs[i]) as u32
-    });
+impl std::error::Error for ItemError {}
 
-    Some(priorities.sum())
+// The set of items in a compartment or bag, with a bit per priority: bit 0 for 'a', up to bit 51
+// for 'Z'. Any character which isn't an item is returned instead.
+fn items(compartment: &str) -> Result<u64, char> {
+    compartment
+        .chars()
+        .try_fold(0, |set, ch| Ok(set | 1 << (priority(ch)? - 1)))
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let score = parse(input)
-        .chunks(3)
-        .map(|bags| {
-            assert!(bags.len() == 3);
-
-            let mut map: HashMap<char, u32> = HashMap::new();
-            let mut badge: Option<char> = None;
+// Sums the priorities of the item in both compartments of each sack.
+fn compartment_sum(input: &str) -> Result<u32, ItemError> {
+    parse(input)
+        .iter()
+        .enumerate()
+        .map(|(i, sack)| {
+            let error = |item| ItemError { item, line: i + 1 };
+
+            // Check every item before splitting, as halving the line by bytes could land in the
+            // middle of a character which isn't an item.
+            let priorities = sack
+                .chars()
+                .map(priority)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(error)?;
+
+            // we can assume there are an even number of items, but check
+            assert!(
+                priorities.len() % 2 == 0,
+                "expected sack to be of even item size"
+            );
+            assert!(!priorities.is_empty(), "sack contains no items");
+
+            let set = |half: &[u8]| half.iter().fold(0u64, |set, p| set | 1 << (p - 1));
+            let (left, right) = priorities.split_at(priorities.len() / 2);
+            let common = set(left) & set(right);
+
+            assert!(common != 0, "no item is in both compartments");
+
+            Ok(common.trailing_zeros() + 1)
+        })
+        .sum()
+}
 
-            'bag: for bag in bags {
-                let mut set = HashSet::new();
+pub fn part_one(input: &str) -> Option<u32> {
+    Some(compartment_sum(input).unwrap_or_else(|err| panic!("{}", err)))
+}
 
-                for ch in bag.chars() {
-                    if set.contains(&ch) {
-                        continue;
-                    }
+// Splits the sacks into groups of the given size and sums the priorities of each group's badge, the
+// one item which every sack in the group has.
+pub fn badge_sum(input: &str, group_size: usize) -> Result<u32, ItemError> {
+    assert!(group_size > 0, "groups need at least one sack");
 
-                    set.insert(ch);
+    parse(input)
+        .chunks(group_size)
+        .enumerate()
+        .map(|(group, bags)| {
+            assert!(bags.len() == group_size, "the last group is short");
 
-                    let score = map.entry(ch).and_modify(|e| *e += 1).or_insert(1);
+            let mut badge = u64::MAX;
 
-                    if *score == 3 {
-                        badge = Some(ch);
-                        break 'bag;
-                    }
-                }
+            for (i, bag) in bags.iter().enumerate() {
+                badge &= items(bag).map_err(|item| ItemError {
+                    item,
+                    line: group * group_size + i + 1,
+                })?;
             }
 
-            assert!(badge.is_some());
+            assert!(badge != 0, "no item is in every bag of the group");
 
-            priority(badge.unwrap()) as u32
+            Ok(badge.trailing_zeros() + 1)
         })
-        .sum();
+        .sum()
+}
 
-    Some(score)
+pub fn part_two(input: &str) -> Option<u32> {
+    Some(badge_sum(input, 3).unwrap_or_else(|err| panic!("{}", err)))
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 3);
+    let input = &advent_of_code::read_input_or_stdin(3);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
@@ -113,13 +125,54 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 3);
+        let input = advent_of_code::read_example(3).unwrap();
         assert_eq!(part_one(&input), Some(157));
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 3);
+        let input = advent_of_code::read_example(3).unwrap();
         assert_eq!(part_two(&input), Some(70));
     }
+
+    #[test]
+    fn test_not_an_item() {
+        assert_eq!(priority('7'), Err('7'));
+        assert_eq!(
+            compartment_sum("abcb\nab1b"),
+            Err(ItemError { item: '1', line: 2 })
+        );
+        assert_eq!(
+            compartment_sum("abab\naéb"),
+            Err(ItemError {
+                item: 'é', line: 2
+            })
+        );
+        assert_eq!(
+            badge_sum("abc\nbcd\nxyZ\nZ Z", 2).unwrap_err().to_string(),
+            "' ' on line 4 is not an item"
+        );
+    }
+
+    #[test]
+    fn test_badge_sum_pairs() {
+        // b is the only item in both of the first pair, and Z in both of the second
+        assert_eq!(badge_sum("abc\nbde\nxyZ\nZZw", 2), Ok(2 + 52));
+
+        let input = advent_of_code::read_example(3).unwrap();
+        assert_eq!(badge_sum(&input, 3), Ok(70));
+    }
+
+    #[test]
+    #[should_panic(expected = "groups need at least one sack")]
+    fn test_badge_sum_empty_groups() {
+        let _ = badge_sum("abc\nbde", 0);
+    }
+
+    #[test]
+    fn test_part_one_late_duplicate() {
+        // the shared item is the last of each compartment, and sorts after everything else
+        assert_eq!(part_one("abcZdefZ"), Some(52));
+        assert_eq!(part_one("aa"), Some(1));
+    }
 }
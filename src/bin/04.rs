@@ -1,55 +1,67 @@
-/// Really simple one today. I thought weekends were meant to be harder?! I started writing a
-/// proper Range data type with parsing logic but it's just as simple to write it like this. My
-/// part two overlap logic can be simpler.
+//! Really simple one today. I thought weekends were meant to be harder?! I started writing a
+//! proper Range data type with parsing logic but it's just as simple to write it like this. My
+//! part two overlap logic can be simpler.
 
-fn elf_range(range: &str) -> (u32, u32) {
+// The sections an elf is assigned, inclusive at both ends.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Range {
+    from: u32,
+    to: u32,
+}
+
+impl Range {
+    // Whether every section of other is also in this range.
+    pub fn fully_contains(&self, other: &Range) -> bool {
+        self.from <= other.from && other.to <= self.to
+    }
+
+    // Whether the ranges share at least one section.
+    pub fn overlaps(&self, other: &Range) -> bool {
+        self.from <= other.to && other.from <= self.to
+    }
+}
+
+fn elf_range(range: &str) -> Range {
     let (from, to) = range.split_once('-').unwrap();
+    let (from, to): (u32, u32) = (from.parse().unwrap(), to.parse().unwrap());
+
+    // A range written back to front would otherwise contain and overlap nothing, and quietly
+    // throw the counts off.
+    Range {
+        from: from.min(to),
+        to: from.max(to),
+    }
+}
+
+fn pairs(input: &str) -> impl Iterator<Item = (Range, Range)> + '_ {
+    input.lines().map(|pair| {
+        let (elf1, elf2) = pair.split_once(',').unwrap();
+
+        (elf_range(elf1), elf_range(elf2))
+    })
+}
 
-    (from.parse().unwrap(), to.parse().unwrap())
+// How many pairs have one elf's range fully containing the other's, and how many overlap at all,
+// from a single pass over the pairs.
+pub fn counts(input: &str) -> (u32, u32) {
+    pairs(input).fold((0, 0), |(contained, overlaps), (elf1, elf2)| {
+        (
+            contained + (elf1.fully_contains(&elf2) || elf2.fully_contains(&elf1)) as u32,
+            overlaps + elf1.overlaps(&elf2) as u32,
+        )
+    })
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let overlaps = input
-        .lines()
-        .filter_map(|pair| {
-            let (elf1, elf2) = pair.split_once(',').unwrap();
-
-            let (e1f, e1t) = elf_range(elf1);
-            let (e2f, e2t) = elf_range(elf2);
-
-            if (e1f <= e2f && e1t >= e2t) || (e2f <= e1f && e2t >= e1t) {
-                Some(())
-            } else {
-                None
-            }
-        })
-        .count();
-
-    Some(overlaps as u32)
+    Some(counts(input).0)
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let overlaps = input
-        .lines()
-        .filter_map(|pair| {
-            let (elf1, elf2) = pair.split_once(',').unwrap();
-
-            let (e1f, e1t) = elf_range(elf1);
-            let (e2f, e2t) = elf_range(elf2);
-
-            if (e1f <= e2f && e1t >= e2f) || (e2f <= e1f && e2t >= e1f) {
-                Some(())
-            } else {
-                None
-            }
-        })
-        .count();
-
-    Some(overlaps as u32)
+    Some(counts(input).1)
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 4);
+    let input = &advent_of_code::read_input_or_stdin(4);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
@@ -60,13 +72,50 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 4);
+        let input = advent_of_code::read_example(4).unwrap();
         assert_eq!(part_one(&input), Some(2));
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 4);
+        let input = advent_of_code::read_example(4).unwrap();
         assert_eq!(part_two(&input), Some(4));
     }
+
+    #[test]
+    fn test_counts() {
+        let input = advent_of_code::read_example(4).unwrap();
+        assert_eq!(counts(&input), (2, 4));
+
+        // contained, overlapping, disjoint, then contained again but written back to front
+        assert_eq!(counts("1-9,3-4\n1-5,5-8\n1-2,4-5\n8-2,3-7"), (2, 3));
+        assert_eq!(elf_range("8-2"), Range { from: 2, to: 8 });
+    }
+
+    #[test]
+    fn test_range() {
+        let range = |from, to| Range { from, to };
+
+        // identical
+        assert!(range(2, 5).fully_contains(&range(2, 5)));
+        assert!(range(2, 5).overlaps(&range(2, 5)));
+
+        // adjacent but disjoint
+        assert!(!range(2, 4).overlaps(&range(5, 7)));
+        assert!(!range(5, 7).overlaps(&range(2, 4)));
+        assert!(!range(2, 4).fully_contains(&range(5, 7)));
+
+        // sharing just an end
+        assert!(range(2, 5).overlaps(&range(5, 7)));
+        assert!(!range(2, 5).fully_contains(&range(5, 7)));
+
+        // one inside another, which only works one way round
+        assert!(range(2, 8).fully_contains(&range(3, 7)));
+        assert!(!range(3, 7).fully_contains(&range(2, 8)));
+        assert!(range(3, 7).overlaps(&range(2, 8)));
+
+        // a single section
+        assert!(range(6, 6).fully_contains(&range(6, 6)));
+        assert!(range(4, 6).fully_contains(&range(6, 6)));
+    }
 }
@@ -3,7 +3,7 @@
 // go after seeing the first part. Parsing logic could be nicer and would have been slightly easier
 // if the number of stacks was known a priori, i.e. put the indices line first.
 
-use std::collections::VecDeque;
+use std::{collections::VecDeque, fmt::Display};
 
 #[derive(Debug)]
 struct Move {
@@ -13,112 +13,177 @@ struct Move {
 }
 
 fn parse(input: &str) -> (Vec<VecDeque<String>>, Vec<Move>) {
-    let mut stacks: Vec<VecDeque<String>> = vec![];
+    // The drawing of the stacks runs up to the first blank line, with the stack numbers at its foot.
+    let mut drawing: Vec<&str> = input.lines().take_while(|l| !l.trim().is_empty()).collect();
+    let index = drawing.pop().expect("missing the line of stack numbers");
+
+    let mut stacks: Vec<VecDeque<String>> = vec![VecDeque::new(); index.split_whitespace().count()];
     let mut moves: Vec<Move> = vec![];
 
-    for line in input.lines() {
-        if line.starts_with("move") {
-            let mut it = line.split_whitespace().skip(1);
-
-            let qty: usize = it.next().unwrap().parse().expect("quantity");
-            assert!(it.next().expect("from") == "from");
-            let from: usize = it.next().unwrap().parse().expect("from");
-            assert!(it.next().expect("to") == "to");
-            let to: usize = it.next().unwrap().parse().expect("to");
-
-            moves.push(Move {
-                quantity: qty,
-                from,
-                to,
-            });
-        } else if line.contains("1") {
-            continue;
-        } else if line.is_empty() {
-            continue;
-        } else {
-            // dbg!(line);
-
-            let mut stack = 0;
-            let chars = line.chars().collect::<Vec<char>>();
-
-            let mut i = 0;
-
-            loop {
-                if i >= chars.len() {
-                    break;
-                }
-
-                if stacks.len() < stack + 1 {
-                    // push a new VecDeque as we found a new stack
-                    stacks.push(VecDeque::new());
-                }
-
-                let crate_id = chars[i + 1];
-                i += 4;
-
-                if !crate_id.is_whitespace() {
-                    stacks[stack].push_back(crate_id.to_string());
-                }
-
-                stack += 1;
+    for line in drawing {
+        // each crate is drawn as "[X] ", so the ids are every fourth character
+        for (stack, crate_id) in line.chars().skip(1).step_by(4).enumerate() {
+            if !crate_id.is_whitespace() {
+                assert!(
+                    stack < stacks.len(),
+                    "crate {} is beyond the last stack",
+                    crate_id
+                );
+                stacks[stack].push_back(crate_id.to_string());
             }
         }
     }
 
-    // println!("{:?} {:?}", stacks, moves);
+    for line in input.lines().filter(|l| l.starts_with("move")) {
+        let mut it = line.split_whitespace().skip(1);
+
+        let qty: usize = it.next().unwrap().parse().expect("quantity");
+        assert!(it.next().expect("from") == "from");
+        let from: usize = it.next().unwrap().parse().expect("from");
+        assert!(it.next().expect("to") == "to");
+        let to: usize = it.next().unwrap().parse().expect("to");
+
+        moves.push(Move {
+            quantity: qty,
+            from,
+            to,
+        });
+    }
 
     (stacks, moves)
 }
 
-pub fn part_one(input: &str) -> Option<String> {
-    let (mut stacks, moves) = parse(input);
+// The crates on top of each stack, skipping any stacks left empty.
+fn tops(stacks: &[VecDeque<String>]) -> String {
+    stacks
+        .iter()
+        .filter_map(|stack| stack.front())
+        .map(|crate_id| crate_id.to_string())
+        .collect::<Vec<String>>()
+        .join("")
+}
 
-    for mv in moves {
-        for _ in 0..mv.quantity {
-            let crate_id = stacks[mv.from - 1].pop_front().unwrap();
-            stacks[mv.to - 1].push_front(crate_id);
+// Why a move can't be made.
+#[derive(Debug, PartialEq, Eq)]
+enum MoveError {
+    NoSuchStack(usize),
+    NotEnoughCrates {
+        stack: usize,
+        wanted: usize,
+        found: usize,
+    },
+}
+
+impl Display for MoveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoSuchStack(stack) => write!(f, "there is no stack {}", stack),
+            Self::NotEnoughCrates {
+                stack,
+                wanted,
+                found,
+            } => write!(
+                f,
+                "stack {} has {} crates, not the {} to move",
+                stack, found, wanted
+            ),
         }
     }
+}
 
-    let tops = stacks
-        .iter()
-        .map(|stack| stack.front().unwrap().to_string())
-        .collect::<Vec<String>>()
-        .join("");
+impl std::error::Error for MoveError {}
 
-    Some(tops)
+// A move in the procedure which couldn't be made, numbered from 1.
+#[derive(Debug, PartialEq, Eq)]
+struct ProcedureError {
+    step: usize,
+    error: MoveError,
 }
 
-pub fn part_two(input: &str) -> Option<String> {
-    let (mut stacks, moves) = parse(input);
+impl Display for ProcedureError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "move {}: {}", self.step, self.error)
+    }
+}
 
-    // nice, we can make a FIFO out of two stacks
+impl std::error::Error for ProcedureError {}
 
-    for mv in moves {
-        let mut tmp = vec![];
+// Something that can carry out the rearrangement procedure, one move at a time. The stacks are
+// stored with their top crate at the front.
+trait CrateMover {
+    fn apply(&self, stacks: &mut [VecDeque<String>], mv: &Move) -> Result<(), MoveError>;
+}
 
-        for _ in 0..mv.quantity {
-            let crate_id = stacks[mv.from - 1].pop_front().unwrap();
-            tmp.push(crate_id);
+// Lifts the top crates off a stack, topmost first, having checked that the move makes sense.
+fn lift(stacks: &mut [VecDeque<String>], mv: &Move) -> Result<Vec<String>, MoveError> {
+    for stack in [mv.from, mv.to] {
+        if stack == 0 || stack > stacks.len() {
+            return Err(MoveError::NoSuchStack(stack));
         }
+    }
+
+    let from = &mut stacks[mv.from - 1];
+
+    if from.len() < mv.quantity {
+        return Err(MoveError::NotEnoughCrates {
+            stack: mv.from,
+            wanted: mv.quantity,
+            found: from.len(),
+        });
+    }
+
+    Ok(from.drain(..mv.quantity).collect())
+}
 
-        while !tmp.is_empty() {
-            let item = tmp.pop().unwrap();
-            stacks[mv.to - 1].push_front(item);
+// Moves crates one at a time, so they land in the reverse order.
+struct CrateMover9000;
+
+impl CrateMover for CrateMover9000 {
+    fn apply(&self, stacks: &mut [VecDeque<String>], mv: &Move) -> Result<(), MoveError> {
+        for crate_id in lift(stacks, mv)? {
+            stacks[mv.to - 1].push_front(crate_id);
         }
+
+        Ok(())
     }
+}
 
-    let tops = stacks
-        .iter()
-        .map(|stack| stack.front().unwrap().to_string())
-        .collect::<Vec<String>>()
-        .join("");
+// Moves all the crates at once, so they keep their order.
+struct CrateMover9001;
 
-    Some(tops)
+impl CrateMover for CrateMover9001 {
+    fn apply(&self, stacks: &mut [VecDeque<String>], mv: &Move) -> Result<(), MoveError> {
+        for crate_id in lift(stacks, mv)?.into_iter().rev() {
+            stacks[mv.to - 1].push_front(crate_id);
+        }
+
+        Ok(())
+    }
+}
+
+// Runs the whole procedure with the given crane, returning the crates left on top.
+fn run(input: &str, mover: &impl CrateMover) -> Result<String, ProcedureError> {
+    let (mut stacks, moves) = parse(input);
+
+    for (i, mv) in moves.iter().enumerate() {
+        mover
+            .apply(&mut stacks, mv)
+            .map_err(|error| ProcedureError { step: i + 1, error })?;
+    }
+
+    Ok(tops(&stacks))
+}
+
+pub fn part_one(input: &str) -> Option<String> {
+    Some(run(input, &CrateMover9000).unwrap_or_else(|err| panic!("{}", err)))
+}
+
+pub fn part_two(input: &str) -> Option<String> {
+    Some(run(input, &CrateMover9001).unwrap_or_else(|err| panic!("{}", err)))
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 5);
+    let input = &advent_of_code::read_input_or_stdin(5);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
@@ -129,13 +194,98 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 5);
+        let input = advent_of_code::read_example(5).unwrap();
         assert_eq!(part_one(&input), Some("CMZ".to_string()));
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 5);
+        let input = advent_of_code::read_example(5).unwrap();
         assert_eq!(part_two(&input), Some("MCD".to_string()));
     }
+
+    #[test]
+    fn test_movers() {
+        let input = advent_of_code::read_example(5).unwrap();
+        assert_eq!(run(&input, &CrateMover9000), Ok("CMZ".to_string()));
+        assert_eq!(run(&input, &CrateMover9001), Ok("MCD".to_string()));
+
+        // a single move of three crates, top to bottom A, B, C
+        let mv = Move {
+            quantity: 3,
+            from: 1,
+            to: 2,
+        };
+        let stacks = || {
+            vec![
+                VecDeque::from(["A", "B", "C"].map(String::from)),
+                VecDeque::new(),
+            ]
+        };
+
+        let mut one_at_a_time = stacks();
+        CrateMover9000.apply(&mut one_at_a_time, &mv).unwrap();
+        assert_eq!(one_at_a_time[1], ["C", "B", "A"]);
+
+        let mut all_at_once = stacks();
+        CrateMover9001.apply(&mut all_at_once, &mv).unwrap();
+        assert_eq!(all_at_once[1], ["A", "B", "C"]);
+        assert!(all_at_once[0].is_empty());
+    }
+
+    #[test]
+    fn test_invalid_moves() {
+        let drawing = "[A]\n[B] [C]\n 1   2\n\n";
+
+        // the second move is to a stack that isn't there
+        let input = format!("{}move 1 from 1 to 2\nmove 1 from 2 to 3\n", drawing);
+        let err = run(&input, &CrateMover9000).unwrap_err();
+        assert_eq!(
+            err,
+            ProcedureError {
+                step: 2,
+                error: MoveError::NoSuchStack(3)
+            }
+        );
+        assert_eq!(err.to_string(), "move 2: there is no stack 3");
+
+        // after the first move there's only one crate left on stack 1
+        let input = format!("{}move 1 from 1 to 2\nmove 2 from 1 to 2\n", drawing);
+        for err in [
+            run(&input, &CrateMover9000).unwrap_err(),
+            run(&input, &CrateMover9001).unwrap_err(),
+        ] {
+            assert_eq!(
+                err.to_string(),
+                "move 2: stack 1 has 1 crates, not the 2 to move"
+            );
+        }
+
+        // stacks are numbered from 1
+        let input = format!("{}move 1 from 0 to 1\n", drawing);
+        assert_eq!(
+            run(&input, &CrateMover9001).unwrap_err().error,
+            MoveError::NoSuchStack(0)
+        );
+    }
+
+    #[test]
+    fn test_parse_sparse_stacks() {
+        // the rightmost stack only has crates lower down, and the middle one has none at all
+        let input = "[A]\n[B]     [C]\n[D]     [E]\n 1   2   3\n\nmove 1 from 3 to 2\n";
+        let (stacks, moves) = parse(input);
+
+        assert_eq!(
+            stacks,
+            vec![
+                VecDeque::from(["A".to_string(), "B".to_string(), "D".to_string()]),
+                VecDeque::new(),
+                VecDeque::from(["C".to_string(), "E".to_string()]),
+            ]
+        );
+        assert_eq!(moves.len(), 1);
+        assert_eq!(tops(&stacks), "AC");
+
+        assert_eq!(part_one(input), Some("ACE".to_string()));
+    }
 }
@@ -1,51 +1,55 @@
+use advent_of_code::helpers::{Grid, Point};
 use itertools::{
     FoldWhile::{Continue, Done},
     Itertools,
 };
 use std::borrow::Borrow;
 
-fn parse(input: &str) -> Vec<Vec<u32>> {
-    input
-        .lines()
-        .map(|line| line.chars().map(|x| x.to_digit(10).unwrap()).collect())
-        .collect()
+fn parse(input: &str) -> Grid<u32> {
+    Grid::from_str_with(input, |ch| ch.to_digit(10).ok_or(ch)).expect("parsing tree heights")
 }
 
-fn columnise(rows: &Vec<Vec<u32>>) -> Vec<Vec<u32>> {
-    (0..rows[0].len())
-        .map(|col| rows.iter().map(|row| row[col]).collect())
-        .collect()
+// The heights of the trees looking out from a tree towards each edge of the grid, nearest first, in
+// the order north, east, south and west.
+fn sightlines(trees: &Grid<u32>, Point { x, y }: Point) -> [Vec<u32>; 4] {
+    let row = trees.row(y);
+
+    [
+        trees
+            .col(x)
+            .rev()
+            .skip(trees.height() - y)
+            .copied()
+            .collect(),
+        row[x + 1..].to_vec(),
+        trees.col(x).skip(y + 1).copied().collect(),
+        row[..x].iter().rev().copied().collect(),
+    ]
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
     let trees = parse(input);
     let mut visible = 0u32;
 
-    let columns = columnise(&trees);
-
-    for (i, row) in trees.iter().enumerate() {
-        for (j, &height) in row.iter().enumerate() {
-            // The rules for visibility state there must be trees in both directions along the row
-            // and column that are taller than this tree, otherwise it is visible. Trees on edges
-            // are automatically visible as nothing can occlude them on that edge.
-            //
-            // This is an ugly O(N^2) algorithm but it's okay for inputs of this size. We could be
-            // more sophisticated by doing some memoisation :shrug:
-
-            // In the iterators that follow all() is documented as returning true on an empty
-            // iterator, so we are tracked that all trees in that direction are shorter i.e.
-            // whether current tree is visible or not.
-
-            let taller = |&x| x < height;
-
-            let north = columns[j][0..i].iter().all(taller);
-            let east = row[j + 1..].iter().all(taller);
-            let south = columns[j][i + 1..].iter().all(taller);
-            let west = row[0..j].iter().all(taller);
-
-            if north || east || south || west {
-                visible += 1;
-            }
+    for (point, &height) in trees.enumerate() {
+        // The rules for visibility state there must be trees in both directions along the row
+        // and column that are taller than this tree, otherwise it is visible. Trees on edges
+        // are automatically visible as nothing can occlude them on that edge.
+        //
+        // This is an ugly O(N^2) algorithm but it's okay for inputs of this size. We could be
+        // more sophisticated by doing some memoisation :shrug:
+
+        // In the iterators that follow all() is documented as returning true on an empty
+        // iterator, so we are tracked that all trees in that direction are shorter i.e.
+        // whether current tree is visible or not.
+
+        let taller = |&x: &u32| x < height;
+
+        if sightlines(&trees, point)
+            .iter()
+            .any(|sightline| sightline.iter().all(taller))
+        {
+            visible += 1;
         }
     }
 
@@ -54,7 +58,6 @@ pub fn part_one(input: &str) -> Option<u32> {
 
 pub fn part_two(input: &str) -> Option<u32> {
     let trees = parse(input);
-    let columns = columnise(&trees);
 
     let mut best_score = 0;
 
@@ -80,20 +83,14 @@ pub fn part_two(input: &str) -> Option<u32> {
             .into_inner()
     }
 
-    for (i, row) in trees.iter().enumerate() {
-        for (j, &height) in row.iter().enumerate() {
-            let column = &columns[j];
-
-            let north = visibility(height, column[0..i].iter().rev());
-            let east = visibility(height, &row[j + 1..]);
-            let south = visibility(height, &column[i + 1..]);
-            let west = visibility(height, row[0..j].iter().rev());
-
-            let score = north * east * south * west;
+    for (point, &height) in trees.enumerate() {
+        let score = sightlines(&trees, point)
+            .iter()
+            .map(|sightline| visibility(height, sightline))
+            .product();
 
-            if score > best_score {
-                best_score = score;
-            }
+        if score > best_score {
+            best_score = score;
         }
     }
 
@@ -101,7 +98,7 @@ pub fn part_two(input: &str) -> Option<u32> {
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 8);
+    let input = &advent_of_code::read_input_or_stdin(8);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
@@ -112,13 +109,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 8);
+        let input = advent_of_code::read_example(8).unwrap();
         assert_eq!(part_one(&input), Some(21));
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 8);
+        let input = advent_of_code::read_example(8).unwrap();
         assert_eq!(part_two(&input), Some(8));
     }
 }
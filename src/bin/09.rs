@@ -14,49 +14,7 @@
 mod day09 {
     use std::{fmt::Display, str::FromStr};
 
-    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
-    pub enum Direction {
-        Up,
-        Down,
-        Left,
-        Right,
-    }
-
-    impl Display for Direction {
-        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-            match self {
-                Self::Up => write!(f, "U"),
-                Self::Down => write!(f, "D"),
-                Self::Left => write!(f, "L"),
-                Self::Right => write!(f, "R"),
-            }
-        }
-    }
-
-    #[derive(Debug)]
-    pub struct ParseDirectionError;
-
-    impl std::error::Error for ParseDirectionError {}
-
-    impl Display for ParseDirectionError {
-        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-            write!(f, "ParseDirectionError")
-        }
-    }
-
-    impl FromStr for Direction {
-        type Err = ParseDirectionError;
-
-        fn from_str(s: &str) -> Result<Self, Self::Err> {
-            match s {
-                "U" => Ok(Self::Up),
-                "D" => Ok(Self::Down),
-                "L" => Ok(Self::Left),
-                "R" => Ok(Self::Right),
-                _ => Err(ParseDirectionError),
-            }
-        }
-    }
+    use advent_of_code::helpers::Direction;
 
     #[derive(Debug)]
     pub struct Move {
@@ -72,7 +30,14 @@ mod day09 {
 
     impl Display for Move {
         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-            write!(f, "{} {}", self.dir, self.steps)
+            let dir = match self.dir {
+                Direction::North => "U",
+                Direction::South => "D",
+                Direction::West => "L",
+                Direction::East => "R",
+            };
+
+            write!(f, "{} {}", dir, self.steps)
         }
     }
 
@@ -93,51 +58,49 @@ mod day09 {
         fn from_str(s: &str) -> Result<Self, Self::Err> {
             let (direction, steps) = s.split_once(" ").ok_or(Self::Err {})?;
 
+            // The moves are only ever written as up/down/left/right
+            let dir = match direction {
+                "U" => Direction::North,
+                "D" => Direction::South,
+                "L" => Direction::West,
+                "R" => Direction::East,
+                _ => return Err(ParseMoveError {}),
+            };
+
             Ok(Self::new(
-                direction.parse().map_err(|_| ParseMoveError {})?,
+                dir,
                 steps.parse().map_err(|_| ParseMoveError {})?,
             ))
         }
     }
-
-    #[derive(Clone, Copy, Debug, Hash, Eq, PartialEq)]
-    pub struct Point {
-        pub x: isize,
-        pub y: isize,
-    }
-
-    impl AsRef<Point> for Point {
-        fn as_ref(&self) -> &Point {
-            &self
-        }
-    }
 }
 
 use std::collections::{HashSet, VecDeque};
 
-use day09::{Direction, Move, Point};
+use advent_of_code::helpers::{render_colored, Color, Direction, IPoint};
+use day09::Move;
 
 struct Grid {
-    tail_visits: HashSet<Point>,
+    tail_visits: HashSet<IPoint>,
     rope: Rope,
 }
 
 struct Rope {
-    knots: VecDeque<Point>,
+    knots: VecDeque<IPoint>,
 }
 
 impl Rope {
-    pub fn new(start: &Point, knots: usize) -> Rope {
+    pub fn new(start: &IPoint, knots: usize) -> Rope {
         Rope {
-            knots: (0..knots).map(|_| start.clone()).collect(),
+            knots: (0..knots).map(|_| *start).collect(),
         }
     }
 
-    pub fn head(&self) -> &Point {
+    pub fn head(&self) -> &IPoint {
         self.knots.front().unwrap()
     }
 
-    pub fn tail(&self) -> &Point {
+    pub fn tail(&self) -> &IPoint {
         self.knots.back().unwrap()
     }
 
@@ -146,30 +109,27 @@ impl Rope {
 
         let mut head = self.knots.pop_front().unwrap();
 
-        match dir {
-            Direction::Up => head.y -= 1,
-            Direction::Down => head.y += 1,
-            Direction::Left => head.x -= 1,
-            Direction::Right => head.x += 1,
-        }
+        let (dx, dy) = dir.delta();
+        head.x += dx;
+        head.y += dy;
 
         new_rope.push_front(head);
 
-        for mut knot in self.knots.iter_mut() {
+        for knot in self.knots.iter_mut() {
             let last_knot = new_rope.back().unwrap();
             let (dx, dy) = (last_knot.x - knot.x, last_knot.y - knot.y);
 
             // play catch up with the rest of the rope
-            if dx.abs() <= 1 && dy.abs() <= 1 {
-                // do nothing
+            if *knot == *last_knot || last_knot.neighbours8().contains(knot) {
+                // still touching, so do nothing
             } else if dx == 0 {
-                knot.y = knot.y + dy.signum();
+                knot.y += dy.signum();
             } else if dy == 0 {
-                knot.x = knot.x + dx.signum();
+                knot.x += dx.signum();
             } else {
                 // diagonal
-                knot.x = knot.x + dx.signum();
-                knot.y = knot.y + dy.signum();
+                knot.x += dx.signum();
+                knot.y += dy.signum();
             }
 
             new_rope.push_back(*knot);
@@ -178,7 +138,7 @@ impl Rope {
         self.knots = new_rope;
     }
 
-    pub fn has_knot(&self, p: &Point) -> Option<usize> {
+    pub fn has_knot(&self, p: &IPoint) -> Option<usize> {
         self.knots
             .iter()
             .enumerate()
@@ -189,7 +149,7 @@ impl Rope {
 
 impl Grid {
     pub fn new(knots: usize) -> Grid {
-        let start = Point { x: 0, y: 0 };
+        let start = IPoint { x: 0, y: 0 };
 
         let mut tail_visits = HashSet::new();
         tail_visits.insert(start);
@@ -213,7 +173,7 @@ impl Grid {
         self.tail_visits.len()
     }
 
-    pub fn display_around(&self, p: &Point) -> String {
+    pub fn display_around(&self, p: &IPoint) -> String {
         let mut out = String::new();
 
         let window_size = 20.max(self.rope.knots.len() as isize);
@@ -223,7 +183,7 @@ impl Grid {
 
             for x in p.x - window_size..=p.x + window_size {
                 out.push_str(
-                    if let Some(pos) = self.rope.has_knot(&Point { x, y }) {
+                    if let Some(pos) = self.rope.has_knot(&IPoint { x, y }) {
                         if pos == self.rope.knots.len() - 1 {
                             "T".to_string()
                         } else if pos == 0 {
@@ -231,7 +191,7 @@ impl Grid {
                         } else {
                             format!("{}", pos)
                         }
-                    } else if self.tail_visits.contains(&Point { x, y }) {
+                    } else if self.tail_visits.contains(&IPoint { x, y }) {
                         "#".to_string()
                     } else {
                         ".".to_string()
@@ -256,7 +216,10 @@ fn print_step(m: &Move, grid: &Grid) {
         println!(
             "=======\n{}\n\n{}\n",
             &m,
-            grid.display_around(&grid.rope.head())
+            render_colored(
+                &grid.display_around(grid.rope.head()),
+                &[('H', Color::Red), ('T', Color::Yellow), ('#', Color::Green)]
+            )
         );
     }
 }
@@ -288,7 +251,7 @@ pub fn part_two(input: &str) -> Option<u32> {
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 9);
+    let input = &advent_of_code::read_input_or_stdin(9);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
@@ -299,13 +262,23 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 9);
+        let input = advent_of_code::read_example(9).unwrap();
         assert_eq!(part_one(&input), Some(88));
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 9);
+        let input = advent_of_code::read_example(9).unwrap();
         assert_eq!(part_two(&input), Some(36));
     }
+
+    #[test]
+    fn test_parse_move() {
+        let m: Move = "L 12".parse().unwrap();
+        assert_eq!((m.dir, m.steps), (Direction::West, 12));
+        assert_eq!(m.to_string(), "L 12");
+
+        assert!("N 1".parse::<Move>().is_err());
+        assert!("U".parse::<Move>().is_err());
+    }
 }
@@ -75,6 +75,7 @@ const CRT_SIZE: usize = 240;
 const CRT_ROWS: usize = 6;
 const CRT_WIDTH: usize = CRT_SIZE / CRT_ROWS;
 
+#[allow(clippy::upper_case_acronyms)]
 struct CPU<I: IntoIterator> {
     instrs: I::IntoIter,
     cycle: usize,
@@ -99,9 +100,8 @@ impl<I: IntoIterator<Item = Instruction>> CPU<I> {
     // returns whether there are any more instructions, the cycle count and the previous value of register X
     pub fn tick(&mut self) -> (bool, usize, isize) {
         if self.remaining_ticks == 0 {
-            self.current_instruction = self.instrs.next().and_then(|i| {
+            self.current_instruction = self.instrs.next().inspect(|i| {
                 self.remaining_ticks = i.ticks();
-                Some(i)
             });
         }
 
@@ -135,7 +135,7 @@ impl<I: IntoIterator<Item = Instruction>> CPU<I> {
 
         self.cycle += 1;
         self.remaining_ticks -= 1;
-        return (true, self.cycle, x);
+        (true, self.cycle, x)
     }
 
     pub fn get_crt(&self) -> String {
@@ -205,12 +205,13 @@ pub fn part_two(input: &str) -> Option<String> {
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 10);
+    let input = &advent_of_code::read_input_or_stdin(10);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
 
-const PART_TWO: &str = "##..##..##..##..##..##..##..##..##..##..
+#[cfg(test)]
+pub const PART_TWO: &str = "##..##..##..##..##..##..##..##..##..##..
 ###...###...###...###...###...###...###.
 ####....####....####....####....####....
 #####.....#####.....#####.....#####.....
@@ -223,13 +224,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 10);
+        let input = advent_of_code::read_example(10).unwrap();
         assert_eq!(part_one(&input), Some(13140));
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 10);
+        let input = advent_of_code::read_example(10).unwrap();
         assert_eq!(part_two(&input), Some(PART_TWO.to_string()));
     }
 }
@@ -49,7 +49,7 @@ struct Modular {
 impl Modular {
     fn new(mut remainder: u32, divisor: u32) -> Modular {
         if remainder > divisor {
-            remainder = remainder % divisor;
+            remainder %= divisor;
         }
 
         Modular { remainder, divisor }
@@ -108,6 +108,7 @@ static MUL: Operator = |l: Modular, r: Modular| l * r;
 static ADD: Operator = |l: Modular, r: Modular| l + r;
 
 #[derive(Debug)]
+#[allow(clippy::upper_case_acronyms)]
 enum RHS {
     Old,
     Literal(Modular),
@@ -229,7 +230,7 @@ where
 
             while let Some(item) = monkey.items.borrow_mut().pop() {
                 let worry_level = worry_update(monkey.op.compute(item));
-                let next_monkey = if worry_level.get_remainder() % monkey.test == 0 {
+                let next_monkey = if worry_level.get_remainder().is_multiple_of(monkey.test) {
                     monkey.if_true
                 } else {
                     monkey.if_false
@@ -269,7 +270,7 @@ pub fn part_two(input: &str) -> Option<u64> {
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 11);
+    let input = &advent_of_code::read_input_or_stdin(11);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
@@ -280,13 +281,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 11);
+        let input = advent_of_code::read_example(11).unwrap();
         assert_eq!(part_one(&input), Some(10605));
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 11);
+        let input = advent_of_code::read_example(11).unwrap();
         assert_eq!(part_two(&input), Some(2_713_310_158));
     }
 }
@@ -1,8 +1,13 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 
+use advent_of_code::helpers::{
+    astar, bfs_distances, dijkstra, index_to_point, parse_char_grid, point_to_index, Grid, Point,
+};
+
+// Positions on the map are numbered in row-major order, which makes for cheap graph nodes.
 #[derive(Debug)]
 struct Map {
-    elevations: Vec<PositionType>,
+    elevations: Grid<char>,
     row_length: usize,
     start: usize,
     end: usize,
@@ -15,33 +20,33 @@ fn can_move_to(from: u8, to: u8) -> bool {
 }
 
 impl Map {
-    pub fn new(elevations: Vec<PositionType>, row_length: usize) -> Self {
-        let (start, end) =
-            elevations
-                .iter()
-                .enumerate()
-                .fold((None, None), |(start, end), (i, elevation)| {
-                    (
-                        if elevation.is_start() {
-                            if start.is_none() {
-                                Some(i)
-                            } else {
-                                panic!("multiple starting positions found");
-                            }
+    pub fn new(elevations: Grid<char>, row_length: usize) -> Self {
+        let (start, end) = elevations
+            .iter()
+            .map(|&ch| PositionType::from(ch))
+            .enumerate()
+            .fold((None, None), |(start, end), (i, elevation)| {
+                (
+                    if elevation.is_start() {
+                        if start.is_none() {
+                            Some(i)
                         } else {
-                            start
-                        },
-                        if elevation.is_end() {
-                            if end.is_none() {
-                                Some(i)
-                            } else {
-                                panic!("multiple ending positions found");
-                            }
+                            panic!("multiple starting positions found");
+                        }
+                    } else {
+                        start
+                    },
+                    if elevation.is_end() {
+                        if end.is_none() {
+                            Some(i)
                         } else {
-                            end
-                        },
-                    )
-                });
+                            panic!("multiple ending positions found");
+                        }
+                    } else {
+                        end
+                    },
+                )
+            });
 
         Self {
             elevations,
@@ -52,49 +57,22 @@ impl Map {
     }
 
     pub fn adjacencies(&self) -> HashMap<usize, Vec<usize>> {
-        let adjacent_points_for_point = |point: usize| -> Vec<usize> {
-            vec![
-                // north
-                if point >= self.row_length {
-                    Some(point - self.row_length)
-                } else {
-                    None
-                },
-                // east
-                if (point + 1) % self.row_length != 0 {
-                    Some(point + 1)
-                } else {
-                    None
-                },
-                // south
-                if point < self.elevations.len() - self.row_length {
-                    Some(point + self.row_length)
-                } else {
-                    None
-                },
-                // west
-                if point % self.row_length > 0 {
-                    Some(point - 1)
-                } else {
-                    None
-                },
-            ]
-            .into_iter()
-            .filter_map(|x| x)
-            .collect()
-        };
-
         let mut adjacencies = HashMap::new();
 
-        for (i, position) in self.elevations.iter().enumerate() {
-            let adjacent_points = adjacent_points_for_point(i);
+        for (point, &ch) in self.elevations.enumerate() {
+            let elevation = PositionType::from(ch).elevation();
+
             adjacencies.insert(
-                i,
-                adjacent_points
-                    .into_iter()
-                    .filter(|&to| {
-                        can_move_to(position.elevation(), self.elevations[to].elevation())
+                point_to_index(self.row_length, &point),
+                self.elevations
+                    .orthogonal_neighbours(&point)
+                    .filter(|to| {
+                        can_move_to(
+                            elevation,
+                            PositionType::from(self.elevations[*to]).elevation(),
+                        )
                     })
+                    .map(|to| point_to_index(self.row_length, &to))
                     .collect(),
             );
         }
@@ -102,17 +80,12 @@ impl Map {
         adjacencies
     }
 
-    pub fn point(&self, i: usize) -> (usize, usize) {
-        let y = i / self.row_length;
-        let x = i % self.row_length;
-
-        (x, y)
+    pub fn point(&self, i: usize) -> Point {
+        index_to_point(self.row_length, i)
     }
 
     pub fn manhattan_distance(&self, from: usize, to: usize) -> u32 {
-        let (from, to) = (self.point(from), self.point(to));
-
-        (from.0.abs_diff(to.0) + from.1.abs_diff(to.1)) as u32
+        self.point(from).manhattan(&self.point(to)) as u32
     }
 }
 
@@ -126,29 +99,21 @@ enum PositionType {
 impl PositionType {
     pub fn elevation(&self) -> u8 {
         (match self {
-            Self::Start => 'a' as u8,
-            Self::End => 'z' as u8,
+            Self::Start => b'a',
+            Self::End => b'z',
             Self::NotSpecial(x) => *x,
         }) - (
             // Technically don't need to normalise, but it makes parsing the elevations easier by eye
-            'a' as u8
+            b'a'
         )
     }
 
     pub fn is_start(&self) -> bool {
-        if let Self::Start = self {
-            true
-        } else {
-            false
-        }
+        matches!(self, Self::Start)
     }
 
     pub fn is_end(&self) -> bool {
-        if let Self::End = self {
-            true
-        } else {
-            false
-        }
+        matches!(self, Self::End)
     }
 }
 
@@ -164,93 +129,105 @@ impl From<char> for PositionType {
 
 impl From<&str> for Map {
     fn from(input: &str) -> Self {
-        let row_length = input.lines().nth(1).unwrap().len();
-
-        Map::new(
-            input
-                .lines()
-                .flat_map(|l| {
-                    if l.len() != row_length {
-                        panic!("input map has lines of non-equal length, which is not supported");
-                    }
-                    l.chars().map(|ch| PositionType::from(ch))
-                })
-                .collect(),
-            row_length,
-        )
+        let (elevations, row_length) = parse_char_grid(input);
+
+        if input.lines().any(|l| l.len() != row_length) {
+            panic!("input map has lines of non-equal length, which is not supported");
+        }
+
+        Map::new(elevations, row_length)
     }
 }
 
-fn astar_from_point(map: &Map, from: Vec<usize>) -> u32 {
+fn astar_from_point(map: &Map, from: Vec<usize>) -> Option<u32> {
     let graph = map.adjacencies();
 
-    let h = |point: usize| map.manhattan_distance(point, map.end);
-
-    // A*
-    let mut fringe = HashSet::new();
+    // The cost of all steps is 1
+    astar(
+        from,
+        |point| graph.get(point).into_iter().flatten().map(|&n| (n, 1)),
+        |&point| map.manhattan_distance(point, map.end),
+        |&point| point == map.end,
+    )
+    .map(|(cost, _)| cost)
+}
 
-    let mut fs = vec![u32::MAX; map.elevations.len()];
-    let mut gs = vec![u32::MAX; map.elevations.len()];
+// Dijkstra explores much more of the map, and searches from each start in turn, but has no
+// heuristic to get wrong. It's kept as a reference to check the A* search against.
+fn dijkstra_from_point(map: &Map, from: Vec<usize>) -> Option<u32> {
+    let graph = map.adjacencies();
 
-    for f in from {
-        fs[f] = h(f);
-        gs[f] = 0;
-        fringe.insert(f);
-    }
+    from.into_iter()
+        .filter_map(|start| {
+            dijkstra(
+                start,
+                |point| graph.get(point).into_iter().flatten().map(|&n| (n, 1)),
+                |&point| point == map.end,
+            )
+        })
+        .map(|(cost, _)| cost)
+        .min()
+}
 
-    while !fringe.is_empty() {
-        // If we made the fringe a priority queue this would be easier.
-        let mut fscores: Vec<(usize, u32)> =
-            fringe.iter().map(|&point| (point, fs[point])).collect();
-        fscores.sort_by(|(_, f1), (_, f2)| f1.partial_cmp(f2).unwrap());
+// All the points at the lowest elevation, from which part two may start.
+fn lowest_points(map: &Map) -> Vec<usize> {
+    map.elevations
+        .iter()
+        .map(|&ch| PositionType::from(ch))
+        .enumerate()
+        .filter_map(|(point, elevation)| {
+            if elevation.is_end() || elevation.elevation() != 0 {
+                None
+            } else {
+                Some(point)
+            }
+        })
+        .collect()
+}
 
-        let (cur, _) = fscores.first().unwrap();
-        fringe.remove(cur);
+pub fn part_one(input: &str) -> Option<u32> {
+    let map = Map::from(input);
+    astar_from_point(&map, vec![map.start])
+}
 
-        for &neighbour in &graph[cur] {
-            // The cost of all steps is 1
-            let gscore = gs[*cur] + 1;
-            if gscore < gs[neighbour] {
-                gs[neighbour] = gscore;
-                fs[neighbour] = gscore + h(neighbour);
+pub fn part_one_by_dijkstra(input: &str) -> Option<u32> {
+    let map = Map::from(input);
+    dijkstra_from_point(&map, vec![map.start])
+}
 
-                fringe.insert(neighbour);
-            }
-        }
-    }
+// Every step costs the same, so a plain breadth first search finds the shortest path too. It shares
+// no code with the A* and Dijkstra searches, so makes for an independent check on them.
+pub fn part_one_by_bfs(input: &str) -> Option<u32> {
+    let map = Map::from(input);
+    let graph = map.adjacencies();
 
-    gs[map.end] as u32
+    bfs_distances([map.start], |point| {
+        graph.get(point).into_iter().flatten().copied()
+    })
+    .get(&map.end)
+    .copied()
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn part_two(input: &str) -> Option<u32> {
     let map = Map::from(input);
-    let cost = astar_from_point(&map, vec![map.start]);
+    let graph = map.adjacencies();
 
-    Some(cost)
+    // Spread out from all of the lowest points at once, and whichever reaches the end first is the
+    // nearest.
+    bfs_distances(lowest_points(&map), |point| {
+        graph.get(point).into_iter().flatten().copied()
+    })
+    .get(&map.end)
+    .copied()
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two_by_dijkstra(input: &str) -> Option<u32> {
     let map = Map::from(input);
-    let cost = astar_from_point(
-        &map,
-        map.elevations
-            .iter()
-            .enumerate()
-            .filter_map(|(point, elevation)| {
-                if elevation.is_end() || elevation.elevation() != 0 {
-                    None
-                } else {
-                    Some(point)
-                }
-            })
-            .collect(),
-    );
-
-    Some(cost)
+    dijkstra_from_point(&map, lowest_points(&map))
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 12);
+    let input = &advent_of_code::read_input_or_stdin(12);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
@@ -261,13 +238,24 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 12);
+        let input = advent_of_code::read_example(12).unwrap();
         assert_eq!(part_one(&input), Some(31));
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 12);
+        let input = advent_of_code::read_example(12).unwrap();
         assert_eq!(part_two(&input), Some(29));
     }
+
+    #[test]
+    fn test_manhattan_distance() {
+        let input = advent_of_code::read_example(12).unwrap();
+        let map = Map::from(input.as_str());
+
+        assert_eq!(map.point(map.end), Point { x: 5, y: 2 });
+        assert_eq!(map.manhattan_distance(map.start, map.end), 7);
+        assert_eq!(map.manhattan_distance(map.end, map.start), 7);
+        assert_eq!(map.manhattan_distance(map.end, map.end), 0);
+    }
 }
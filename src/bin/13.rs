@@ -28,19 +28,11 @@ impl Packet {
     }
 
     pub fn is_list(&self) -> bool {
-        if let Self::List(_) = self {
-            true
-        } else {
-            false
-        }
+        matches!(self, Self::List(_))
     }
 
     pub fn is_literal(&self) -> bool {
-        if let Self::Literal(_) = self {
-            true
-        } else {
-            false
-        }
+        matches!(self, Self::Literal(_))
     }
 
     pub fn literal(&self) -> &u32 {
@@ -73,7 +65,7 @@ impl Display for Packet {
         match self {
             Self::Literal(x) => write!(f, "{}", x),
             Self::List(lst) => {
-                let d = lst.into_iter().map(|x| format!("{}", x)).join(",");
+                let d = lst.iter().map(|x| format!("{}", x)).join(",");
 
                 write!(f, "[{}]", d)
             }
@@ -254,7 +246,7 @@ pub fn part_two(input: &str) -> Option<u32> {
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 13);
+    let input = &advent_of_code::read_input_or_stdin(13);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
@@ -265,13 +257,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 13);
+        let input = advent_of_code::read_example(13).unwrap();
         assert_eq!(part_one(&input), Some(13));
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 13);
+        let input = advent_of_code::read_example(13).unwrap();
         assert_eq!(part_two(&input), Some(140));
     }
 }
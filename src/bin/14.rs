@@ -1,25 +1,20 @@
-// This is a very inefficient (space-wise) solution. It models the entire Grid as a 1-dimension
-// vector and simulates the sand falling through this grid (updating only when sand comes to rest).
-// This imposes some challenges that constrain the solution for part 2, but it was a nice easy way
-// of visualising the problem. This really isn't a great approach today.
+// This simulates the sand falling through the cave one grain at a time (updating only when sand
+// comes to rest). It first modelled the entire cave as a dense grid, which was a nice easy way of
+// visualising the problem but had to be made absurdly wide to fit the floor in part 2. Now only the
+// rock and sand are stored, and the floor is just a depth that sand can't fall past.
 
-use std::fmt::Display;
+use std::{fmt::Display, str::FromStr};
 
-use advent_of_code::helpers::{Grid, Point};
+use advent_of_code::helpers::{render_colored, Color, Point, PointParseError, SparseGrid};
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
 enum Space {
+    #[default]
     Air,
     Rock,
     Sand,
 }
 
-impl Default for Space {
-    fn default() -> Self {
-        Self::Air
-    }
-}
-
 impl Display for Space {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -32,219 +27,170 @@ impl Display for Space {
 
 struct Line(Vec<Point>);
 
-impl From<&str> for Line {
-    fn from(s: &str) -> Self {
-        Line(s.split(" -> ").map(|p| Point::from(p)).collect())
-    }
-}
-
-fn parse(input: &str) -> Vec<Line> {
-    input.lines().map(|l| Line::from(l)).collect()
-}
-
-fn format_grid<T: Clone + Default + Display>(grid: &Grid<T>) -> String {
-    let mut s = String::new();
-
-    for (i, value) in grid.iter().enumerate() {
-        if i > 0 && i % grid.width() == 0 {
-            s.push('\n');
-        }
+impl FromStr for Line {
+    type Err = PointParseError;
 
-        s.push_str(format!("{}", value).as_str());
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Line(
+            s.split(" -> ")
+                .map(str::parse)
+                .collect::<Result<Vec<Point>, PointParseError>>()?,
+        ))
     }
-
-    s
 }
 
-fn min_max<T, R, I, P>(items: I, p: P) -> (Option<R>, Option<R>)
-where
-    I: IntoIterator<Item = T>,
-    R: Ord + Copy,
-    P: Fn(&T) -> R,
-{
-    let resolve = |x: Option<R>, y: &R, ord: std::cmp::Ordering| -> Option<R> {
-        if x.is_none() {
-            Some(*y)
-        } else if y.cmp(&x.unwrap()) == ord {
-            Some(*y)
-        } else {
-            x
-        }
-    };
-
-    items.into_iter().fold((None, None), |(min, max), item| {
-        let x = p(&item);
-        (
-            resolve(min, &x, std::cmp::Ordering::Less),
-            resolve(max, &x, std::cmp::Ordering::Greater),
-        )
-    })
+fn parse(input: &str) -> Result<Vec<Line>, PointParseError> {
+    input.lines().map(str::parse).collect()
 }
 
-fn draw_grid(
-    lines: &Vec<Line>,
-    with_floor: bool,
-) -> (Grid<Space>, Box<dyn Fn(usize, usize) -> Point>) {
-    let (min_x, max_x) = min_max(lines.iter().flat_map(|line| &line.0), |point| point.x);
-    let (_, max_y) = min_max(lines.iter().flat_map(|line| &line.0), |point| point.y);
-
-    let max_y = if with_floor {
-        max_y.unwrap() + 2
-    } else {
-        max_y.unwrap()
-    };
-
-    let grid_width = min_x.unwrap().abs_diff(max_x.unwrap()) + 1;
-    let (grid_width, min_x) = if with_floor {
-        // just make the grid mega wide if there's a floor so that we stand a change of the sand
-        // blocking the spigot before we run out of space. A better solution would be to be able to
-        // expand a grid's width.
-
-        // this was just trial and error to figure out what size grid would give us enough space to
-        // fill and block the spigot.
-        (grid_width + 1000, min_x.unwrap() - 300)
-    } else {
-        (grid_width, min_x.unwrap())
-    };
-
-    let mut grid = Grid::new(grid_width, max_y);
-
-    // fill in the floor
-    for x in 0..grid.width() {
-        *grid.point_mut(&Point { x, y: max_y }) = Space::Rock;
-    }
+// Colours for printing the cave, so the sand stands out from the rock.
+const LEGEND: [(char, Color); 2] = [('o', Color::Yellow), ('#', Color::Blue)];
 
-    println!("{}", format_grid(&grid));
+// The rock and sand in the cave. Anywhere that isn't stored is air.
+struct Cave {
+    spaces: SparseGrid<Space>,
+    // The bottom of the lowest rock, below which sand falls forever unless there's a floor.
+    lowest_rock: usize,
+    floor: bool,
+}
 
-    let make_point = move |x, y| Point { x: x - min_x, y };
+impl Cave {
+    fn new(lines: &Vec<Line>, floor: bool) -> Cave {
+        let mut spaces = SparseGrid::new();
 
-    for line in lines {
-        for (from, to) in line.0.iter().zip(line.0.iter().skip(1)) {
-            if (from.x == to.x && from.y == to.y) || (from.x != to.x && from.y != to.y) {
-                panic!("line is too complicated");
-            } else if from.x == to.x {
-                let x = from.x;
-                for y in from.y.min(to.y)..=from.y.max(to.y) {
-                    *grid.point_mut(&make_point(x, y)) = Space::Rock;
+        for line in lines {
+            for (from, to) in line.0.iter().zip(line.0.iter().skip(1)) {
+                if (from.x == to.x && from.y == to.y) || (from.x != to.x && from.y != to.y) {
+                    panic!("line is too complicated");
                 }
-            } else if from.y == to.y {
-                let y = from.y;
+
                 for x in from.x.min(to.x)..=from.x.max(to.x) {
-                    *grid.point_mut(&make_point(x, y)) = Space::Rock;
+                    for y in from.y.min(to.y)..=from.y.max(to.y) {
+                        spaces.set(Point { x, y }, Space::Rock);
+                    }
                 }
             }
         }
+
+        let lowest_rock = spaces.bounds().expect("no rock in the cave").1.y;
+
+        Cave {
+            spaces,
+            lowest_rock,
+            floor,
+        }
     }
 
-    (grid, Box::new(make_point))
+    fn is_blocked(&self, point: &Point) -> bool {
+        (self.floor && point.y == self.lowest_rock + 2) || self.spaces.contains(point)
+    }
+
+    fn sand(&self) -> usize {
+        self.spaces
+            .iter()
+            .filter(|(_, &space)| space == Space::Sand)
+            .count()
+    }
+
+    fn render(&self) -> String {
+        render_colored(&format!("{}\n", self.spaces.render('.')), &LEGEND)
+    }
 }
 
 static SPIGOT: Point = Point { x: 500, y: 0 };
 
-fn add_grain<F>(grid: &mut Grid<Space>, make_point: &F) -> Option<Point>
-where
-    F: Fn(usize, usize) -> Point,
-{
-    let mut sand = make_point(SPIGOT.x, SPIGOT.y);
+fn add_grain(cave: &mut Cave) -> Option<Point> {
+    let mut sand = SPIGOT;
 
     // Can we make something at the spigot?
-    if *grid.point(&sand) != Space::Air {
+    if cave.is_blocked(&sand) {
         return None;
     }
 
     loop {
-        let next = vec![
-            Some(Point {
-                x: sand.x,
-                y: sand.y + 1,
-            }),
-            if sand.x.checked_sub(1).is_none() {
-                None
-            } else {
-                Some(Point {
-                    x: sand.x - 1,
-                    y: sand.y + 1,
-                })
-            },
-            Some(Point {
-                x: sand.x + 1,
-                y: sand.y + 1,
-            }),
-        ];
-
-        let mut found_next = false;
-
-        for candidate in next {
-            if candidate.is_none()
-                || candidate.unwrap().x >= grid.width()
-                || candidate.unwrap().y >= grid.height()
-            {
-                // The sand would flow out of the grid
-                return None;
-            }
-
-            if *grid.point(&candidate.unwrap()) == Space::Air {
-                // the sand can flow to this candidate
-                sand = candidate.unwrap();
-                found_next = true;
-                break;
-            }
+        if !cave.floor && sand.y > cave.lowest_rock {
+            // There's nothing left to land on
+            return None;
         }
 
-        if !found_next {
+        // straight down, then down and to the left, then down and to the right
+        let next = [
+            sand.translate(0, 1),
+            sand.translate(-1, 1),
+            sand.translate(1, 1),
+        ];
+
+        match next
+            .into_iter()
+            .map(|candidate| candidate.expect("sand flowed off the left of the cave"))
+            .find(|candidate| !cave.is_blocked(candidate))
+        {
+            // the sand can flow to this candidate
+            Some(point) => sand = point,
             // nowhere the sand can go, so it stays here
-            break;
+            None => break,
         }
     }
 
-    *grid.point_mut(&sand) = Space::Sand;
-    return Some(sand);
+    cave.spaces.set(sand, Space::Sand);
+    Some(sand)
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let lines = parse(input);
-    let (mut grid, make_point) = draw_grid(&lines, false);
+    let lines = parse(input).expect("parsing rock paths");
+    let mut cave = Cave::new(&lines, false);
+    print!("{}", cave.render());
 
     // Flow the sand
-    while add_grain(&mut grid, &make_point).is_some() {
-        println!("{}", format_grid(&grid));
+    while add_grain(&mut cave).is_some() {
+        print!("{}", cave.render());
     }
 
-    Some(grid.iter().filter(|&space| *space == Space::Sand).count() as u32)
+    Some(cave.sand() as u32)
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let lines = parse(input);
-    let (mut grid, make_point) = draw_grid(&lines, true);
+    let lines = parse(input).expect("parsing rock paths");
+    let mut cave = Cave::new(&lines, true);
 
     // Flow the sand
-    while add_grain(&mut grid, &make_point).is_some() {
-        // println!("{}", format_grid(&grid));
+    while add_grain(&mut cave).is_some() {
+        // print!("{}", cave.render());
     }
 
-    Some(grid.iter().filter(|&space| *space == Space::Sand).count() as u32)
+    Some(cave.sand() as u32)
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 14);
+    let input = &advent_of_code::read_input_or_stdin(14);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
 
 #[cfg(test)]
-
 mod tests {
     use super::*;
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 14);
+        let input = advent_of_code::read_example(14).unwrap();
         assert_eq!(part_one(&input), Some(24));
     }
 
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(parse("498,4 -> 498,6\n503,4 -> 502,4").unwrap().len(), 2);
+
+        let err = parse("498,4 -> 498,6\n503,4 -> 502,").err().unwrap();
+        assert_eq!(
+            format!("{}", err),
+            "error parsing point \"502,\": missing or invalid y coordinate"
+        );
+    }
+
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 14);
+        let input = advent_of_code::read_example(14).unwrap();
         assert_eq!(part_two(&input), Some(93));
     }
 }
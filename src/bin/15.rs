@@ -1,39 +1,22 @@
-use std::fmt::Display;
-#[derive(Debug)]
-struct Point {
-    x: i64,
-    y: i64,
-}
-
-impl Display for Point {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "x={}, y={}", self.x, self.y)
-    }
-}
+use advent_of_code::helpers::IPoint;
 
 #[derive(Debug)]
 struct Detection {
-    sensor: Point,
-    beacon: Point,
+    sensor: IPoint,
+    beacon: IPoint,
 }
 
-impl Point {
-    fn from_coord(input: &str) -> Point {
-        let (x, y) = input.split_once(", ").unwrap();
+fn from_coord(input: &str) -> IPoint {
+    let (x, y) = input.split_once(", ").unwrap();
 
-        Point {
-            x: x.strip_prefix("x=").unwrap().parse().unwrap(),
-            y: y.strip_prefix("y=").unwrap().parse().unwrap(),
-        }
-    }
-
-    fn manhattan(&self, other: &Point) -> u64 {
-        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    IPoint {
+        x: x.strip_prefix("x=").unwrap().parse().unwrap(),
+        y: y.strip_prefix("y=").unwrap().parse().unwrap(),
     }
+}
 
-    pub fn tuning_frequency(&self) -> u64 {
-        (self.x as u64 * 4_000_000) + self.y as u64
-    }
+fn tuning_frequency(point: &IPoint) -> u64 {
+    (point.x as u64 * 4_000_000) + point.y as u64
 }
 
 fn parse(input: &str) -> Vec<Detection> {
@@ -42,8 +25,8 @@ fn parse(input: &str) -> Vec<Detection> {
     for line in input.lines() {
         let (sensor, beacon) = line.split_once(": ").unwrap();
 
-        let sensor = Point::from_coord(sensor.strip_prefix("Sensor at ").unwrap());
-        let beacon = Point::from_coord(beacon.strip_prefix("closest beacon is at ").unwrap());
+        let sensor = from_coord(sensor.strip_prefix("Sensor at ").unwrap());
+        let beacon = from_coord(beacon.strip_prefix("closest beacon is at ").unwrap());
 
         detections.push(Detection { sensor, beacon });
     }
@@ -51,19 +34,19 @@ fn parse(input: &str) -> Vec<Detection> {
     detections
 }
 
-fn with_distances(detections: &Vec<Detection>) -> Vec<(&Detection, u64)> {
+fn with_distances(detections: &[Detection]) -> Vec<(&Detection, usize)> {
     detections
         .iter()
         .zip(detections.iter().map(|d| d.sensor.manhattan(&d.beacon)))
         .collect()
 }
 
-fn detections_for_row<'a>(detections: &Vec<(&'a Detection, u64)>, y: i64) -> Vec<(i64, i64)> {
-    let relevant_sensors: Vec<(&Detection, u64)> = detections
+fn detections_for_row(detections: &[(&Detection, usize)], y: isize) -> Vec<(isize, isize)> {
+    let relevant_sensors: Vec<(&Detection, usize)> = detections
         .iter()
         .filter_map(|(detection, manhattan)| {
-            if detection.sensor.y - *manhattan as i64 <= y
-                && detection.sensor.y + *manhattan as i64 >= y
+            if detection.sensor.y - *manhattan as isize <= y
+                && detection.sensor.y + *manhattan as isize >= y
             {
                 Some((*detection, *manhattan))
             } else {
@@ -77,13 +60,13 @@ fn detections_for_row<'a>(detections: &Vec<(&'a Detection, u64)>, y: i64) -> Vec
     for (detection, distance) in relevant_sensors {
         let dy = detection.sensor.y.abs_diff(y);
         let (x1, x2) = (
-            detection.sensor.x - distance as i64 + dy as i64,
-            detection.sensor.x + distance as i64 - dy as i64,
+            detection.sensor.x - distance as isize + dy as isize,
+            detection.sensor.x + distance as isize - dy as isize,
         );
         regions.push((x1, x2));
 
         // println!(
-        //     "sensor: {}; beacon: {}; d={}, dy={}, xs=({},{})",
+        //     "sensor: {:?}; beacon: {:?}; d={}, dy={}, xs=({},{})",
         //     detection.sensor, detection.beacon, distance, dy, x1, x2,
         // );
     }
@@ -96,10 +79,10 @@ pub fn part_one(input: &str) -> Option<u32> {
     let detections = parse(input);
     let with_distances = with_distances(&detections);
 
-    const SEARCH_Y: i64 = if cfg!(test) { 10 } else { 2_000_000 };
+    const SEARCH_Y: isize = if cfg!(test) { 10 } else { 2_000_000 };
     let regions = detections_for_row(&with_distances, SEARCH_Y);
 
-    let mut beacons_in_row: Vec<i64> = detections
+    let mut beacons_in_row: Vec<isize> = detections
         .iter()
         .filter_map(|d| {
             if d.beacon.y == SEARCH_Y {
@@ -115,7 +98,7 @@ pub fn part_one(input: &str) -> Option<u32> {
     let mut monitored = 0;
 
     {
-        let mut cur_x = i64::MIN;
+        let mut cur_x = isize::MIN;
 
         for (left, right) in regions.iter() {
             let start = if cur_x > *right {
@@ -150,7 +133,7 @@ pub fn part_two(input: &str) -> Option<u64> {
     let detections = parse(input);
     let with_distances = with_distances(&detections);
 
-    const SEARCH_XY: i64 = if cfg!(test) { 20 } else { 4_000_000 };
+    const SEARCH_XY: isize = if cfg!(test) { 20 } else { 4_000_000 };
     let mut point = None;
 
     'rows: for row in 0..=SEARCH_XY {
@@ -166,7 +149,7 @@ pub fn part_two(input: &str) -> Option<u64> {
                 println!("The point is ({},{})", cur + 1, row);
                 dbg!(low, high, cur, row);
 
-                point = Some(Point { x: cur + 1, y: row });
+                point = Some(IPoint { x: cur + 1, y: row });
                 break 'rows;
             } else {
                 cur = high;
@@ -174,11 +157,11 @@ pub fn part_two(input: &str) -> Option<u64> {
         }
     }
 
-    Some(point.unwrap().tuning_frequency() as u64)
+    Some(tuning_frequency(&point.unwrap()))
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 15);
+    let input = &advent_of_code::read_input_or_stdin(15);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
@@ -189,13 +172,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 15);
+        let input = advent_of_code::read_example(15).unwrap();
         assert_eq!(part_one(&input), Some(26));
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 15);
+        let input = advent_of_code::read_example(15).unwrap();
         assert_eq!(part_two(&input), Some(56_000_011));
     }
 }
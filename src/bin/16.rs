@@ -11,13 +11,13 @@ fn parse(input: &str) -> HashMap<String, (u32, Vec<String>)> {
     let mut valves = HashMap::new();
 
     lazy_static! {
-        static ref re: Regex = Regex::new(
+        static ref RE: Regex = Regex::new(
             r"(?m)^Valve ([A-Z]+) has flow rate=(\d+); tunnels? leads? to valves? (.+)$"
         )
         .unwrap();
     }
 
-    for caps in re.captures_iter(input) {
+    for caps in RE.captures_iter(input) {
         let (name, flow_rate, tunnels) = (caps[1].to_string(), &caps[2], &caps[3].to_string());
 
         let flow_rate: u32 = flow_rate.parse().unwrap();
@@ -43,7 +43,7 @@ fn floyd(graph: &HashMap<String, (u32, Vec<String>)>) -> HashMap<String, HashMap
         }
 
         for connection in &graph[key].1 {
-            dist[id_of(key)][id_of(&connection)] = 1;
+            dist[id_of(key)][id_of(connection)] = 1;
         }
     }
 
@@ -142,12 +142,14 @@ fn hash_valves(s: &HashSet<String>) -> u64 {
     hash.finish()
 }
 
+type PathCache = HashMap<(String, usize, u64), (u32, Vec<String>)>;
+
 fn brute_force<'a>(
     state: &mut State,
     flow_rates: &HashMap<String, u32>,
     costs: &HashMap<String, HashMap<String, u32>>,
-    best_paths: &RefCell<&'a mut HashMap<(String, usize, u64), (u32, Vec<String>)>>,
-    best_q_paths: &RefCell<&'a mut HashMap<(String, usize, u64), (u32, Vec<String>)>>,
+    best_paths: &RefCell<&'a mut PathCache>,
+    best_q_paths: &RefCell<&'a mut PathCache>,
 ) -> (u32, Vec<String>) {
     // Vec<(String, u32, usize)>) {
     let memo_key = &(
@@ -166,8 +168,7 @@ fn brute_force<'a>(
         let cached = cache.borrow();
         let cached = cached.get(memo_key);
 
-        if cached.is_some() {
-            let (flow, valves) = cached.unwrap();
+        if let Some((flow, valves)) = cached {
             // println!(
             //     "hit cache {} {} {:?} = {} {:?}",
             //     state.current_node, state.mins_remaining, &state.open_valves, flow, &valves
@@ -181,20 +182,14 @@ fn brute_force<'a>(
 
             return (
                 state.flow + flow,
-                state
-                    .open_valves
-                    .iter()
-                    .map(|x| x.clone())
-                    .collect::<Vec<String>>(),
+                state.open_valves.iter().cloned().collect::<Vec<String>>(),
             );
         }
     }
 
     let mut new_flow = 0;
 
-    if state.current_node.ends_with("+")
-        && !state.open_valves.contains(&state.current_node.to_string())
-    {
+    if state.current_node.ends_with("+") && !state.open_valves.contains(state.current_node) {
         state.open_valves.insert(state.current_node.to_string());
         new_flow +=
             state.mins_remaining as u32 * flow_rates[state.current_node.trim_end_matches('+')];
@@ -214,7 +209,7 @@ fn brute_force<'a>(
 
     let mut next_node_candidates: Vec<(String, &u32)> = costs[state.current_node]
         .iter()
-        .filter(|(neighbour, cost)| filter_next_nodes((&neighbour, cost)))
+        .filter(|(neighbour, cost)| filter_next_nodes((neighbour, cost)))
         .map(|(neighbour, cost)| (neighbour.clone(), cost))
         .collect();
 
@@ -244,20 +239,14 @@ fn brute_force<'a>(
                 steps: state.steps.clone(),
             };
 
-            let result = brute_force(&mut state, flow_rates, costs, best_paths, best_q_paths);
-
-            result
+            brute_force(&mut state, flow_rates, costs, best_paths, best_q_paths)
         })
         .sorted_by_key(|(flow, _)| *flow)
         .last();
 
     let default = (
         state.flow + new_flow,
-        state
-            .open_valves
-            .iter()
-            .map(|x| x.clone())
-            .collect::<Vec<String>>(),
+        state.open_valves.iter().cloned().collect::<Vec<String>>(),
     );
 
     let result = result.unwrap_or(default);
@@ -347,7 +336,7 @@ pub fn part_two(input: &str) -> Option<u32> {
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 16);
+    let input = &advent_of_code::read_input_or_stdin(16);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
@@ -358,13 +347,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 16);
+        let input = advent_of_code::read_example(16).unwrap();
         assert_eq!(part_one(&input), Some(1651));
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 16);
+        let input = advent_of_code::read_example(16).unwrap();
         assert_eq!(part_two(&input), Some(1707));
     }
 }
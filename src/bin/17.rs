@@ -1,7 +1,8 @@
+use advent_of_code::helpers::verbose;
 use rstar::RTree;
 use std::{
     cell::RefCell,
-    collections::{hash_map::DefaultHasher, HashMap, HashSet},
+    collections::{hash_map::DefaultHasher, HashMap},
     hash::{Hash, Hasher},
 };
 
@@ -81,11 +82,11 @@ static FALL_ORDER: [Shape; 5] = [
     Shape::Square,
 ];
 
-fn tree_contains_shape(tree: &RTree<(i64, i64)>, points: &Vec<(i64, i64)>) -> bool {
+fn tree_contains_shape(tree: &RTree<(i64, i64)>, points: &[(i64, i64)]) -> bool {
     points.iter().any(|point| tree.contains(point))
 }
 
-fn max_height(points: &Vec<(i64, i64)>) -> i64 {
+fn max_height(points: &[(i64, i64)]) -> i64 {
     *points.iter().map(|(_, y)| y).max().unwrap() + 1
 }
 
@@ -133,7 +134,7 @@ where
     for j in (0..=start_height).rev() {
         let next_object = shape.starting_geometry((left, j));
 
-        if tree_contains_shape(&tree, &next_object) {
+        if tree_contains_shape(tree, &next_object) {
             // crash
             let max_height = max_height(&object);
             return (object, max_height);
@@ -164,7 +165,8 @@ where
     (object, max_height)
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
+// Simulates every one of the given number of rock drops and returns the height of the tower.
+pub fn tower_height_by_simulation(input: &str, rocks: usize) -> u64 {
     let directions = parse(input);
     let mut jet_blasts = directions.iter().cycle();
     let mut next_shape = FALL_ORDER.iter().cycle();
@@ -172,7 +174,7 @@ pub fn part_one(input: &str) -> Option<u64> {
     let mut tree: RTree<(i64, i64)> = RTree::new();
     let mut max_height = 0;
 
-    for i in 0..2022 {
+    for _i in 0..rocks {
         let start_height = max_height + 3;
 
         let shape = next_shape.next().unwrap();
@@ -185,7 +187,16 @@ pub fn part_one(input: &str) -> Option<u64> {
         max_height = new_max_height.max(max_height);
     }
 
-    Some(max_height as u64)
+    if verbose() && tree.size() > 0 {
+        // The whole tower is thousands of rows, so just show the top of it.
+        print!("{}", print_tree(&tree, (max_height - 20).max(0)));
+    }
+
+    max_height as u64
+}
+
+pub fn part_one(input: &str) -> Option<u64> {
+    Some(tower_height_by_simulation(input, 2022))
 }
 
 // It is totally infeasible to simulate a trillion rock drops and keep tracking state of the cave,
@@ -200,7 +211,7 @@ pub fn part_one(input: &str) -> Option<u64> {
 // rock record. Also be advised that the cycle does not necessarily start from rock 0, nor is the
 // number of cycles from the end of the cycle necessarily a whole cycle's worth of rock drops; it
 // is necessary to partially simulate a cycle at the end to retrieve the overall cave height.
-pub fn part_two(input: &str) -> Option<u64> {
+pub fn tower_height_by_cycle(input: &str, rocks: i64) -> u64 {
     let find_first_cycle = || {
         // This is the y height of the top rock in each column. This is used later to generate the
         // minimum possible sized state to find cycles; observe that we can effectively ignore the
@@ -320,44 +331,50 @@ pub fn part_two(input: &str) -> Option<u64> {
     // retrieved for each step.
     let (cycle_end, cycle_start, cycle_step_heights) = find_first_cycle();
 
-    const TARGET: i64 = 1_000_000_000_000;
+    // If the tower is finished before the cycle repeats, we already know its height.
+    if rocks <= cycle_end as i64 {
+        return cycle_step_heights[rocks as usize] as u64;
+    }
 
     let cycle_length = cycle_end.abs_diff(cycle_start); // Offset by 1 but of no consequence to us as we
                                                         // just care about cycle length.
-    let steps_remaining = TARGET - cycle_end as i64; // this is not guaranteed to be an integer
-                                                     // number of cycles! Need to clean up a
-                                                     // partial cycle later (maybe)
+    let steps_remaining = rocks - cycle_end as i64; // this is not guaranteed to be an integer
+                                                    // number of cycles! Need to clean up a
+                                                    // partial cycle later (maybe)
 
     let cycles_remaining = steps_remaining / cycle_length as i64;
 
     let cycle_height_change = cycles_remaining
         * (cycle_step_heights[cycle_end as usize] - cycle_step_heights[cycle_start as usize]);
 
-    let partial_cycle = TARGET - (cycles_remaining * cycle_length as i64) - cycle_end as i64;
+    let partial_cycle = rocks - (cycles_remaining * cycle_length as i64) - cycle_end as i64;
 
     let partial_cycle_height_change = cycle_step_heights
         [(cycle_start as i64 + partial_cycle) as usize]
         - cycle_step_heights[cycle_start as usize];
 
-    dbg!(
-        cycle_length,
-        steps_remaining,
-        cycles_remaining,
-        partial_cycle,
-        partial_cycle_height_change,
-    );
-
-    Some(
-        // The total height is thus the original height at the end of the cycle, plus the change from
-        // playing the cycle forward cycles_remaining times, plus the partial height change from
-        // partially playing forward one cycle until we reach the target number of dropped rocks.
-        (cycle_step_heights.last().unwrap() + cycle_height_change + partial_cycle_height_change)
-            as u64,
-    )
+    if verbose() {
+        dbg!(
+            cycle_length,
+            steps_remaining,
+            cycles_remaining,
+            partial_cycle,
+            partial_cycle_height_change,
+        );
+    }
+
+    // The total height is thus the original height at the end of the cycle, plus the change from
+    // playing the cycle forward cycles_remaining times, plus the partial height change from
+    // partially playing forward one cycle until we reach the target number of dropped rocks.
+    (cycle_step_heights.last().unwrap() + cycle_height_change + partial_cycle_height_change) as u64
+}
+
+pub fn part_two(input: &str) -> Option<u64> {
+    Some(tower_height_by_cycle(input, 1_000_000_000_000))
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 17);
+    let input = &advent_of_code::read_input_or_stdin(17);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
@@ -368,13 +385,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 17);
+        let input = advent_of_code::read_example(17).unwrap();
         assert_eq!(part_one(&input), Some(3068));
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 17);
+        let input = advent_of_code::read_example(17).unwrap();
         assert_eq!(part_two(&input), Some(1514285714288));
     }
 }
@@ -17,8 +17,8 @@ impl From<(i8, i8, i8)> for Coord {
 
 impl Coord {
     pub fn dot(self, other: Self) -> i32 {
-        self.0 as i32 as i32 * other.0 as i32 as i32
-            + self.1 as i32 as i32 * other.1 as i32 as i32
+        self.0 as i32 * other.0 as i32
+            + self.1 as i32 * other.1 as i32
             + self.2 as i32 * other.2 as i32
     }
 }
@@ -51,6 +51,7 @@ macro_rules! coord {
     };
 }
 
+#[allow(clippy::derived_hash_with_manual_eq)]
 #[derive(Clone, Debug, Eq, Hash)]
 struct Plane {
     n: Coord,
@@ -190,7 +191,7 @@ pub fn part_one(input: &str) -> Option<u32> {
 "
     );
 
-    Some(exterior_surface_area(&all_planes(&coords)))
+    Some(exterior_surface_area(all_planes(&coords)))
 }
 
 // The general principle for solving part 2 is to find the surface area of all unconnected faces
@@ -235,10 +236,11 @@ pub fn part_one(input: &str) -> Option<u32> {
 pub fn part_two(input: &str) -> Option<u32> {
     let coords = parse(input).expect("parsing coordinates");
 
-    fn find_min_max<F: Fn(&Coord) -> i32>(coords: &Vec<Coord>, f: F) -> (i32, i32) {
-        coords.iter().map(|coord| f(coord)).fold(
-            (i32::MAX, i32::MIN),
-            |(mut min, mut max), item| {
+    fn find_min_max<F: Fn(&Coord) -> i32>(coords: &[Coord], f: F) -> (i32, i32) {
+        coords
+            .iter()
+            .map(f)
+            .fold((i32::MAX, i32::MIN), |(mut min, mut max), item| {
                 if item < min {
                     min = item;
                 }
@@ -247,8 +249,7 @@ pub fn part_two(input: &str) -> Option<u32> {
                 }
 
                 (min, max)
-            },
-        )
+            })
     }
 
     let (x_min, x_max) = find_min_max(&coords, |c| c.0 as i32);
@@ -260,17 +261,8 @@ pub fn part_two(input: &str) -> Option<u32> {
     let pos = |coord: &Coord| {
         let (x, y, z) = (coord.0 as i32, coord.1 as i32, coord.2 as i32);
 
-        if x as i32 >= x_min
-            && x as i32 <= x_max
-            && y as i32 >= y_min
-            && y as i32 <= y_max
-            && z as i32 >= z_min
-            && z as i32 <= z_max
-        {
-            Some(
-                ((z as i32 * (x_max + 1) * (y_max + 1)) + (y as i32 * (x_max + 1)) + x as i32)
-                    as usize,
-            )
+        if x >= x_min && x <= x_max && y >= y_min && y <= y_max && z >= z_min && z <= z_max {
+            Some(((z * (x_max + 1) * (y_max + 1)) + (y * (x_max + 1)) + x) as usize)
         } else {
             // The coordinate provided is out of bounds for the problem space.
             None
@@ -279,12 +271,12 @@ pub fn part_two(input: &str) -> Option<u32> {
 
     for coord in &coords {
         let pos = pos(coord).unwrap();
-        assert!(states[pos] == false);
+        assert!(!states[pos]);
 
         states[pos] = true;
     }
 
-    let mut faces = exterior_surface_area(&all_planes(&coords));
+    let mut faces = exterior_surface_area(all_planes(&coords));
     let mut possible_air_gap = HashSet::new();
 
     let adjacents = |coord: &Coord| {
@@ -356,7 +348,7 @@ pub fn part_two(input: &str) -> Option<u32> {
     for pocket in &possible_air_gap {
         faces -= adjacents(pocket)
             .iter()
-            .filter_map(|adj| pos(adj))
+            .filter_map(&pos)
             .filter(|&p| states[p])
             .count() as u32;
     }
@@ -364,8 +356,68 @@ pub fn part_two(input: &str) -> Option<u32> {
     Some(faces)
 }
 
+// The straightforward way to count faces for part one: every face of every cube that doesn't have
+// another cube against it. This is the reference the planes are checked against.
+pub fn surface_area_by_neighbours(input: &str) -> u32 {
+    let coords: HashSet<Coord> = parse(input)
+        .expect("parsing coordinates")
+        .into_iter()
+        .collect();
+
+    coords
+        .iter()
+        .flat_map(|&coord| PLANES.iter().map(move |&(_, normal)| coord + normal))
+        .filter(|adjacent| !coords.contains(adjacent))
+        .count() as u32
+}
+
+// The straightforward way to count faces for part two: flood fill the air from just outside the
+// droplet, and count every face of a cube that the air touches. This is the reference the air
+// pocket pruning is checked against.
+pub fn exterior_surface_area_by_flood_fill(input: &str) -> u32 {
+    let coords: HashSet<Coord> = parse(input)
+        .expect("parsing coordinates")
+        .into_iter()
+        .collect();
+
+    // Leave a layer of air around the droplet so that the fill can get all the way round it.
+    let min = coords
+        .iter()
+        .fold(coord!(i8::MAX, i8::MAX, i8::MAX), |m, c| {
+            coord!(m.0.min(c.0 - 1), m.1.min(c.1 - 1), m.2.min(c.2 - 1))
+        });
+    let max = coords
+        .iter()
+        .fold(coord!(i8::MIN, i8::MIN, i8::MIN), |m, c| {
+            coord!(m.0.max(c.0 + 1), m.1.max(c.1 + 1), m.2.max(c.2 + 1))
+        });
+    let in_bounds = |c: &Coord| {
+        (min.0..=max.0).contains(&c.0)
+            && (min.1..=max.1).contains(&c.1)
+            && (min.2..=max.2).contains(&c.2)
+    };
+
+    let mut faces = 0;
+    let mut air = HashSet::from([min]);
+    let mut frontier = vec![min];
+
+    while let Some(coord) = frontier.pop() {
+        for &(_, normal) in &PLANES {
+            let adjacent = coord + normal;
+
+            if coords.contains(&adjacent) {
+                faces += 1;
+            } else if in_bounds(&adjacent) && air.insert(adjacent) {
+                frontier.push(adjacent);
+            }
+        }
+    }
+
+    faces
+}
+
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 18);
+    let input = &advent_of_code::read_input_or_stdin(18);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
@@ -376,13 +428,20 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 18);
+        let input = advent_of_code::read_example(18).unwrap();
         assert_eq!(part_one(&input), Some(64));
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 18);
+        let input = advent_of_code::read_example(18).unwrap();
         assert_eq!(part_two(&input), Some(58));
     }
+
+    #[test]
+    fn test_reference_implementations() {
+        let input = advent_of_code::read_example(18).unwrap();
+        assert_eq!(surface_area_by_neighbours(&input), 64);
+        assert_eq!(exterior_surface_area_by_flood_fill(&input), 58);
+    }
 }
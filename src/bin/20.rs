@@ -2,7 +2,7 @@ fn parse(input: &str) -> Vec<i64> {
     input.lines().map(|x| x.parse().unwrap()).collect()
 }
 
-fn mix(file: &Vec<i64>, iterations: usize) -> Vec<i64> {
+fn mix(file: &[i64], iterations: usize) -> Vec<i64> {
     let mut intermediate: Vec<(i64, usize)> =
         file.iter().enumerate().map(|(i, x)| (*x, i)).collect();
 
@@ -44,7 +44,7 @@ fn mix(file: &Vec<i64>, iterations: usize) -> Vec<i64> {
     intermediate.iter().map(|(x, _)| *x).collect()
 }
 
-fn grove_coords(mixed: &Vec<i64>) -> i64 {
+fn grove_coords(mixed: &[i64]) -> i64 {
     let zero = mixed.iter().position(|&x| x == 0).unwrap();
 
     let get_elt = |n: usize| {
@@ -63,14 +63,14 @@ pub fn part_one(input: &str) -> Option<i64> {
 }
 
 pub fn part_two(input: &str) -> Option<i64> {
-    let file = parse(input).iter().map(|&x| x * 811589153).collect();
+    let file: Vec<i64> = parse(input).iter().map(|&x| x * 811589153).collect();
     let mixed = mix(&file, 10);
 
     Some(grove_coords(&mixed))
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 20);
+    let input = &advent_of_code::read_input_or_stdin(20);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
@@ -81,13 +81,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 20);
+        let input = advent_of_code::read_example(20).unwrap();
         assert_eq!(part_one(&input), Some(3));
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 20);
+        let input = advent_of_code::read_example(20).unwrap();
         assert_eq!(part_two(&input), Some(1623178306));
     }
 }
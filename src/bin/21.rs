@@ -1,5 +1,7 @@
 use std::{collections::HashMap, fmt::Display, str::FromStr};
 
+use advent_of_code::helpers::ParseError;
+
 #[derive(Clone, Debug)]
 enum Op {
     Add,
@@ -8,19 +10,8 @@ enum Op {
     Div,
 }
 
-#[derive(Debug)]
-struct OpParseError {}
-
-impl Display for OpParseError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "error parsing op")
-    }
-}
-
-impl std::error::Error for OpParseError {}
-
 impl FromStr for Op {
-    type Err = OpParseError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
@@ -28,7 +19,7 @@ impl FromStr for Op {
             "-" => Ok(Self::Sub),
             "*" => Ok(Self::Mul),
             "/" => Ok(Self::Div),
-            _ => Err(OpParseError {}),
+            _ => Err(ParseError::new("op", s)),
         }
     }
 }
@@ -66,17 +57,6 @@ enum Job {
     Operation(Op, String, String),
 }
 
-#[derive(Debug)]
-struct JobParseError {}
-
-impl Display for JobParseError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "error parsing job")
-    }
-}
-
-impl std::error::Error for JobParseError {}
-
 impl Display for Job {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -87,23 +67,23 @@ impl Display for Job {
 }
 
 impl FromStr for Job {
-    type Err = JobParseError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         if let Ok(literal) = s.parse::<i64>() {
             Ok(Self::Yell(literal))
         } else {
-            let mut parts = s.split_whitespace();
+            let parts: Vec<&str> = s.split_whitespace().collect();
 
-            let dep1 = parts.next().unwrap();
-            let op = parts
-                .next()
-                .unwrap()
-                .parse::<Op>()
-                .map_err(|_| Self::Err {})?;
-            let dep2 = parts.next().unwrap();
+            let [dep1, op, dep2] = parts[..] else {
+                return Err(ParseError::new("job", s));
+            };
 
-            Ok(Self::Operation(op, dep1.to_string(), dep2.to_string()))
+            Ok(Self::Operation(
+                op.parse()?,
+                dep1.to_string(),
+                dep2.to_string(),
+            ))
         }
     }
 }
@@ -118,11 +98,13 @@ impl Job {
     }
 }
 
-fn parse(input: &str) -> Result<HashMap<String, Job>, Box<dyn std::error::Error>> {
+fn parse(input: &str) -> Result<HashMap<String, Job>, ParseError> {
     let mut map = HashMap::new();
 
     for line in input.lines() {
-        let (monkey, directive) = line.split_once(": ").unwrap();
+        let (monkey, directive) = line
+            .split_once(": ")
+            .ok_or_else(|| ParseError::new("monkey", line))?;
         map.insert(monkey.to_string(), directive.parse()?);
     }
 
@@ -137,12 +119,9 @@ fn explore(jobs: &HashMap<String, Job>, start_at: &str) -> Vec<String> {
         let mut next = vec![];
 
         for item in &explore {
-            match &jobs[item] {
-                Job::Operation(_, lhs, rhs) => {
-                    q.extend(vec![lhs.clone(), rhs.clone()]);
-                    next.extend(vec![lhs.clone(), rhs.clone()]);
-                }
-                _ => {}
+            if let Job::Operation(_, lhs, rhs) = &jobs[item] {
+                q.extend(vec![lhs.clone(), rhs.clone()]);
+                next.extend(vec![lhs.clone(), rhs.clone()]);
             }
         }
 
@@ -161,11 +140,9 @@ fn reduce(jobs: &HashMap<String, Job>, start: &str) -> i64 {
         }
     }
 
-    let mut q = explore(&jobs, start);
-
-    while !q.is_empty() {
-        let item = q.pop().unwrap();
+    let mut q = explore(jobs, start);
 
+    while let Some(item) = q.pop() {
         match &jobs[&item] {
             Job::Yell(x) => {
                 results.insert(item, *x);
@@ -269,7 +246,7 @@ pub fn part_two(input: &str) -> Option<i64> {
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 21);
+    let input = &advent_of_code::read_input_or_stdin(21);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
@@ -280,13 +257,29 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 21);
+        let input = advent_of_code::read_example(21).unwrap();
         assert_eq!(part_one(&input), Some(152));
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 21);
+        let input = advent_of_code::read_example(21).unwrap();
         assert_eq!(part_two(&input), Some(301));
     }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse("root: pppw % sjmn").unwrap_err(),
+            ParseError::new("op", "%")
+        );
+        assert_eq!(
+            parse("root: pppw +").unwrap_err(),
+            ParseError::new("job", "pppw +")
+        );
+        assert_eq!(
+            parse("root pppw + sjmn").unwrap_err().to_string(),
+            "error parsing monkey \"root pppw + sjmn\""
+        );
+    }
 }
@@ -1,15 +1,17 @@
 use std::{
+    collections::HashMap,
     fmt::Display,
     str::{FromStr, Lines},
 };
 
-use advent_of_code::helpers::{Grid, Point};
+use advent_of_code::helpers::{verbose, Direction, Grid, ParseError, Point};
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
 enum Cell {
+    #[default]
     Nothingness, // Just here to make storing the map as a uniform rectangular shape easier.
-    Open,        // Places we can move, i.e. "."
-    Wall,        // Places we cannot move, i.e. "#"
+    Open, // Places we can move, i.e. "."
+    Wall, // Places we cannot move, i.e. "#"
 }
 
 impl Display for Cell {
@@ -27,44 +29,27 @@ impl Display for Cell {
 }
 
 impl TryFrom<char> for Cell {
-    type Error = CellStringParseError;
+    type Error = ParseError;
 
     fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
             '.' => Ok(Self::Open),
             '#' => Ok(Self::Wall),
             ' ' => Ok(Self::Nothingness),
-            _ => Err(Self::Error {}),
+            _ => Err(ParseError::new("cell", value)),
         }
     }
 }
 
-impl Default for Cell {
-    fn default() -> Self {
-        Self::Nothingness
-    }
-}
-
-#[derive(Debug)]
-struct CellStringParseError {}
-
-impl Display for CellStringParseError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "error parsing cell string")
-    }
-}
-
-impl std::error::Error for CellStringParseError {}
-
 impl FromStr for Cell {
-    type Err = CellStringParseError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            " " => Ok(Self::Nothingness),
-            "." => Ok(Self::Open),
-            "#" => Ok(Self::Wall),
-            _ => Err(Self::Err {}),
+        let mut chars = s.chars();
+
+        match (chars.next(), chars.next()) {
+            (Some(ch), None) => ch.try_into(),
+            _ => Err(ParseError::new("cell", s)),
         }
     }
 }
@@ -84,36 +69,27 @@ impl Default for CellState {
     }
 }
 
+// Cells we walked through show the direction we were facing as we left them, so displaying the
+// grid draws the path over the map.
 impl Display for CellState {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if let Some(visited) = self.visited.get() {
-            (visited).fmt(f)
+            visited.arrow().fmt(f)
         } else {
             (self.cell).fmt(f)
         }
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 enum Instruction {
     Forward(usize),
     Clockwise,
     Anticlockwise,
 }
 
-#[derive(Debug)]
-struct ParseInstructionError {}
-
-impl Display for ParseInstructionError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "error parsing instruction")
-    }
-}
-
-impl std::error::Error for ParseInstructionError {}
-
 impl FromStr for Instruction {
-    type Err = ParseInstructionError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         if let Ok(steps) = s.parse::<usize>() {
@@ -122,92 +98,70 @@ impl FromStr for Instruction {
             match s {
                 "L" => Ok(Self::Anticlockwise),
                 "R" => Ok(Self::Clockwise),
-                _ => Err(Self::Err {}),
+                _ => Err(ParseError::new("instruction", s)),
             }
         }
     }
 }
 
-fn parse(input: Lines) -> (Grid<CellState>, Vec<Instruction>) {
-    let grid = {
-        let (map_lines, longest_line) = {
-            let mut longest_line = 0;
-            let lines: Vec<&str> = input
-                .clone()
-                .take_while(|l| !l.is_empty())
-                .inspect(|l| {
-                    if l.len() > longest_line {
-                        longest_line = l.len();
-                    }
-                })
-                .collect();
-
-            (lines, longest_line)
-        };
-
-        let mut grid = Grid::new(longest_line, map_lines.len());
-
-        for row in 0..map_lines.len() {
-            for col in 0..longest_line {
-                *grid.point_mut(&Point { x: col, y: row }) = {
-                    let ch = if let Some(ch) = map_lines[row].chars().nth(col) {
-                        ch.try_into().unwrap()
-                    } else {
-                        break;
-                    };
+// Splits the instruction line into runs of digits, each of which is a number of steps forward, and
+// single turn characters.
+fn parse_instructions(line: &str) -> Result<Vec<Instruction>, ParseError> {
+    let mut instructions = vec![];
+    let mut steps = String::new();
 
-                    CellState {
-                        cell: ch,
-                        visited: std::cell::Cell::new(None),
-                    }
-                }
-            }
+    for ch in line.trim().chars() {
+        if ch.is_ascii_digit() {
+            steps.push(ch);
+            continue;
         }
 
-        grid
-    };
-
-    let instructions = {
-        let mut instructions = vec![];
-        let mut cur = String::new();
+        if !steps.is_empty() {
+            instructions.push(steps.parse()?);
+            steps.clear();
+        }
 
-        let mut it = input.last().unwrap().chars();
+        instructions.push(ch.to_string().parse()?);
+    }
 
-        loop {
-            let ch = it.next();
+    if !steps.is_empty() {
+        instructions.push(steps.parse()?);
+    }
 
-            if ch.is_none()
-                || (!cur.is_empty()
-                    && cur.chars().last().unwrap().is_digit(10) != ch.unwrap().is_digit(10))
-            {
-                // Change of type so record the instruction
-                instructions.push(cur.parse().unwrap());
-                cur.clear();
-            }
+    Ok(instructions)
+}
 
-            if ch.is_some() {
-                cur.push(ch.unwrap());
-            } else {
-                break;
-            }
-        }
+fn parse(input: Lines) -> (Grid<CellState>, Vec<Instruction>) {
+    let map: Vec<&str> = input.clone().take_while(|l| !l.is_empty()).collect();
 
-        instructions
-    };
+    let grid = Grid::from_str_with(&map.join("\n"), |ch| {
+        Cell::try_from(ch).map(|cell| CellState {
+            cell,
+            visited: std::cell::Cell::new(None),
+        })
+    })
+    .expect("parsing map");
+
+    let instructions = parse_instructions(
+        input
+            .skip_while(|l| !l.is_empty())
+            .find(|l| !l.trim().is_empty())
+            .unwrap_or_default(),
+    )
+    .expect("parsing instructions");
 
     (grid, instructions)
 }
 
-#[derive(Clone, Copy, Debug)]
-enum Direction {
-    North = 0,
-    East = 1,
-    South = 2,
-    West = 3,
+// The bits of facing a direction which are particular to today's puzzle.
+trait Facing {
+    fn score(&self) -> u32;
+    fn turn(self, instr: &Instruction) -> Self;
+    fn arrow(&self) -> char;
 }
 
-impl Direction {
-    pub fn score(&self) -> u32 {
+impl Facing for Direction {
+    fn score(&self) -> u32 {
         match self {
             Self::North => 3,
             Self::East => 0,
@@ -215,158 +169,364 @@ impl Direction {
             Self::West => 2,
         }
     }
-}
 
-impl Display for Direction {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                Self::North => '^',
-                Self::East => '>',
-                Self::South => '⌄',
-                Self::West => '<',
-            }
-        )
+    fn turn(self, instr: &Instruction) -> Direction {
+        match instr {
+            Instruction::Clockwise => self.turn_clockwise(),
+            Instruction::Anticlockwise => self.turn_anticlockwise(),
+            Instruction::Forward(_) => self,
+        }
+    }
+
+    // How the path is drawn when rendering the map.
+    fn arrow(&self) -> char {
+        match self {
+            Self::North => '^',
+            Self::East => '>',
+            Self::South => '⌄',
+            Self::West => '<',
+        }
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let (grid, instructions) = parse(input.lines());
-
-    let (mut x, mut y) = (
-        (0..grid.width())
-            .find(|&x| grid.point(&Point { x, y: 0 }).cell == Cell::Open)
-            .unwrap(),
-        0,
-    );
-
-    let mut direction = Direction::East;
-
-    fn can_wrap<F: Fn(usize) -> Point, I: Iterator<Item = usize>>(
-        grid: &Grid<CellState>,
-        it: I,
-        point_fn: F,
-    ) -> Option<usize> {
-        for candidate in it {
-            match grid.point(&point_fn(candidate)).cell {
-                Cell::Nothingness => {
-                    continue;
+// Part two folds the map into a cube. Each face of the cube is a square block of the map, numbered
+// in the order the faces are encountered scanning the map top to bottom, left to right. Each face
+// has four sides, one per direction, so side `face * 4 + direction` identifies an edge of a face.
+//
+// Folding is a matter of finding which sides are glued together. Faces which touch on the flat map
+// are trivially connected. For the rest, consider any face with two connected sides at a corner,
+// e.g. its north and east sides. The faces on the other end of those two sides must themselves
+// share an edge around that corner of the cube, which is the side immediately anticlockwise of the
+// arrival side on the first and clockwise of the arrival side on the second. Repeatedly applying
+// this rule closes up the whole cube.
+struct Cube {
+    size: usize,
+    faces: Vec<Point>,
+    connections: HashMap<usize, usize>,
+}
+
+impl Cube {
+    pub fn new(grid: &Grid<CellState>) -> Cube {
+        let cells = grid
+            .iter()
+            .filter(|cell| cell.cell != Cell::Nothingness)
+            .count();
+        let size = ((cells / 6) as f64).sqrt() as usize;
+
+        let mut faces = vec![];
+
+        for y in (0..grid.height() / size).map(|y| y * size) {
+            for x in (0..grid.width() / size).map(|x| x * size) {
+                if grid.point(&Point { x, y }).cell != Cell::Nothingness {
+                    faces.push(Point { x, y });
                 }
-                Cell::Open => return Some(candidate),
-                Cell::Wall => return None,
             }
         }
 
-        None
-    }
+        assert_eq!(faces.len(), 6, "map does not have six faces");
 
-    let find_next = |max: usize, cur: usize, rev, point_fn: Box<dyn Fn(usize) -> Point>| {
-        let candidate = if rev { cur.saturating_sub(1) } else { cur + 1 };
+        let side = |face: usize, direction: Direction| face * 4 + direction as usize;
+        let mut connections = HashMap::new();
 
-        if (rev && cur == 0)
-            || (!rev && cur == max)
-            || (*grid.point(&point_fn(candidate))).cell == Cell::Nothingness
-        {
-            let mut range: Box<dyn DoubleEndedIterator<Item = usize>> =
-                Box::new((cur + 1..=max).chain(0..cur));
-            if rev {
-                range = Box::new(range.rev());
-            }
+        let connect = |connections: &mut HashMap<usize, usize>, a: usize, b: usize| {
+            connections.insert(a, b);
+            connections.insert(b, a);
+        };
 
-            if let Some(next) = can_wrap(&grid, range, point_fn) {
-                next
-            } else {
-                cur
+        for (i, a) in faces.iter().enumerate() {
+            for (j, b) in faces.iter().enumerate() {
+                if a.y == b.y && a.x + size == b.x {
+                    connect(
+                        &mut connections,
+                        side(i, Direction::East),
+                        side(j, Direction::West),
+                    );
+                } else if a.x == b.x && a.y + size == b.y {
+                    connect(
+                        &mut connections,
+                        side(i, Direction::South),
+                        side(j, Direction::North),
+                    );
+                }
             }
-        } else if grid.point(&point_fn(candidate)).cell == Cell::Wall {
-            cur
-        } else {
-            candidate
         }
-    };
-
-    for instruction in instructions {
-        println!("{:?} ({},{}) {:?}\n{}", instruction, x, y, direction, grid);
-
-        match instruction {
-            Instruction::Anticlockwise => {
-                direction = match direction {
-                    Direction::North => Direction::West,
-                    Direction::East => Direction::North,
-                    Direction::South => Direction::East,
-                    Direction::West => Direction::South,
-                };
+
+        while connections.len() < 24 {
+            let mut progressed = false;
+
+            for face in 0..faces.len() {
+                for direction in (0..4).map(Direction::from_index) {
+                    let (a, b) = match (
+                        connections.get(&side(face, direction)),
+                        connections.get(&side(face, direction.turn_clockwise())),
+                    ) {
+                        (Some(&a), Some(&b)) => (a, b),
+                        _ => continue,
+                    };
+
+                    let a = side(a / 4, Direction::from_index(a).turn_anticlockwise());
+                    let b = side(b / 4, Direction::from_index(b).turn_clockwise());
+
+                    if !connections.contains_key(&a) {
+                        connect(&mut connections, a, b);
+                        progressed = true;
+                    }
+                }
             }
-            Instruction::Clockwise => {
-                direction = match direction {
-                    Direction::North => Direction::East,
-                    Direction::East => Direction::South,
-                    Direction::South => Direction::West,
-                    Direction::West => Direction::North,
-                };
+
+            assert!(progressed, "map does not fold into a cube");
+        }
+
+        Cube {
+            size,
+            faces,
+            connections,
+        }
+    }
+
+    fn face_of(&self, point: &Point) -> usize {
+        self.faces
+            .iter()
+            .position(|origin| {
+                (origin.x..origin.x + self.size).contains(&point.x)
+                    && (origin.y..origin.y + self.size).contains(&point.y)
+            })
+            .expect("point is not on a face of the cube")
+    }
+
+    // Returns the point one step from the given point in the given direction, and the direction
+    // we'll be facing when we get there, which changes if we walk over the edge of a face.
+    pub fn step(&self, point: &Point, direction: Direction) -> (Point, Direction) {
+        let face = self.face_of(point);
+        let origin = self.faces[face];
+        let last = self.size - 1;
+        let (x, y) = (point.x - origin.x, point.y - origin.y);
+        let (dx, dy) = direction.delta();
+        let (next_x, next_y) = (x as isize + dx, y as isize + dy);
+
+        if (0..self.size as isize).contains(&next_x) && (0..self.size as isize).contains(&next_y) {
+            return (
+                Point {
+                    x: origin.x + next_x as usize,
+                    y: origin.y + next_y as usize,
+                },
+                direction,
+            );
+        }
+
+        // Measure how far along the edge we are, going clockwise around the face. Glued edges run
+        // in opposite directions, so the offset is reversed on the other side.
+        let offset = last
+            - match direction {
+                Direction::North => x,
+                Direction::East => y,
+                Direction::South => last - x,
+                Direction::West => last - y,
+            };
+
+        let arrival = self.connections[&(face * 4 + direction as usize)];
+        let (origin, side) = (self.faces[arrival / 4], Direction::from_index(arrival));
+
+        let (x, y) = match side {
+            Direction::North => (offset, 0),
+            Direction::East => (last, offset),
+            Direction::South => (last - offset, last),
+            Direction::West => (0, last - offset),
+        };
+
+        (
+            Point {
+                x: origin.x + x,
+                y: origin.y + y,
+            },
+            side.opposite(),
+        )
+    }
+}
+
+// Maps a face and the direction we're heading when walking off it to the face we arrive on and the
+// direction we're heading once there. Useful for checking the fold.
+fn edge_connections(input: &str) -> HashMap<(usize, Direction), (usize, Direction)> {
+    let cube = Cube::new(&Board::from(input).grid);
+
+    cube.connections
+        .iter()
+        .map(|(&from, &to)| {
+            (
+                (from / 4, Direction::from_index(from)),
+                (to / 4, Direction::from_index(to).opposite()),
+            )
+        })
+        .collect()
+}
+
+// Draws the map with the direction we were facing as we left each cell we walked through.
+fn render_path(grid: &Grid<CellState>) -> String {
+    grid.render()
+}
+
+struct Board {
+    grid: Grid<CellState>,
+    instructions: Vec<Instruction>,
+}
+
+impl From<&str> for Board {
+    fn from(input: &str) -> Self {
+        let (grid, instructions) = parse(input.lines());
+
+        Board { grid, instructions }
+    }
+}
+
+impl Board {
+    // We start on the leftmost open cell of the top row of the map. The map may not have any open
+    // cells in its top row once padded, so search for the first open cell in the whole grid.
+    fn start(&self) -> Point {
+        self.grid
+            .positions_of(|state| state.cell == Cell::Open)
+            .next()
+            .expect("map has no open cells")
+    }
+
+    // Forgets the path taken by a previous run.
+    fn reset(&self) {
+        self.grid.iter().for_each(|state| state.visited.set(None));
+    }
+
+    // Walks the instructions over the flat map, wrapping around to the other side of the row or
+    // column when we fall off an edge, returning the password.
+    pub fn solve_flat(&self) -> u32 {
+        self.reset();
+
+        let grid = &self.grid;
+        let Point { mut x, mut y } = self.start();
+        let mut direction = Direction::East;
+
+        let find_next = |x: usize, y: usize, direction: Direction| {
+            let (dx, dy) = direction.delta();
+
+            // If we fall off the edge of the map, keep going in the same direction, wrapping around
+            // the grid, until we come back onto the map on the far side.
+            let (mut next_x, mut next_y) = (x as isize + dx, y as isize + dy);
+
+            while grid.wrapping_point(next_x, next_y).cell == Cell::Nothingness {
+                (next_x, next_y) = (next_x + dx, next_y + dy);
             }
-            Instruction::Forward(steps) => {
-                (x, y) = (0..steps).fold((x, y), |(x, y), _| {
-                    let (next_x, next_y) = match direction {
-                        Direction::North => (
-                            x,
-                            find_next(
-                                grid.height() - 1,
-                                y,
-                                true,
-                                Box::new(move |y| Point { x, y }),
-                            ),
-                        ),
-                        Direction::East => (
-                            find_next(
-                                grid.width() - 1,
-                                x,
-                                false,
-                                Box::new(move |x| Point { x, y }),
-                            ),
-                            y,
-                        ),
-                        Direction::South => (
-                            x,
-                            find_next(
-                                grid.height() - 1,
-                                y,
-                                false,
-                                Box::new(move |y| Point { x, y }),
-                            ),
-                        ),
-                        Direction::West => (
-                            find_next(grid.width() - 1, x, true, Box::new(move |x| Point { x, y })),
-                            y,
-                        ),
-                    };
 
-                    grid.point(&Point {
-                        x: next_x,
-                        y: next_y,
-                    })
-                    .visited
-                    .set(Some(direction));
+            let (next_x, next_y) = (
+                next_x.rem_euclid(grid.width() as isize) as usize,
+                next_y.rem_euclid(grid.height() as isize) as usize,
+            );
 
-                    (next_x, next_y)
-                });
+            if grid
+                .point(&Point {
+                    x: next_x,
+                    y: next_y,
+                })
+                .cell
+                == Cell::Wall
+            {
+                (x, y)
+            } else {
+                (next_x, next_y)
             }
         };
+
+        for instruction in &self.instructions {
+            match *instruction {
+                Instruction::Forward(steps) => {
+                    (x, y) = (0..steps).fold((x, y), |(x, y), _| {
+                        let (next_x, next_y) = find_next(x, y, direction);
+
+                        grid.point(&Point {
+                            x: next_x,
+                            y: next_y,
+                        })
+                        .visited
+                        .set(Some(direction));
+
+                        (next_x, next_y)
+                    });
+                }
+                _ => direction = direction.turn(instruction),
+            };
+        }
+
+        (y as u32 + 1) * 1000 + (x as u32 + 1) * 4 + direction.score()
+    }
+
+    // Walks the instructions over the map folded into a cube, returning the password.
+    pub fn solve_cube(&self) -> u32 {
+        self.reset();
+
+        let cube = Cube::new(&self.grid);
+        let mut point = self.start();
+        let mut direction = Direction::East;
+
+        for instruction in &self.instructions {
+            match *instruction {
+                Instruction::Forward(steps) => {
+                    for _ in 0..steps {
+                        let (next, next_direction) = cube.step(&point, direction);
+
+                        if self.grid.point(&next).cell == Cell::Wall {
+                            break;
+                        }
+
+                        (point, direction) = (next, next_direction);
+                        self.grid.point(&point).visited.set(Some(direction));
+                    }
+                }
+                _ => direction = direction.turn(instruction),
+            }
+        }
+
+        (point.y as u32 + 1) * 1000 + (point.x as u32 + 1) * 4 + direction.score()
     }
+}
 
-    Some((y as u32 + 1) * 1000 + (x as u32 + 1) * 4 + direction.score())
+// The same path gives different passwords depending on whether the map wraps flat or as a cube.
+fn both_passwords(input: &str) -> (u32, u32) {
+    let board = Board::from(input);
+
+    (board.solve_flat(), board.solve_cube())
+}
+
+pub fn part_one(input: &str) -> Option<u32> {
+    Some(Board::from(input).solve_flat())
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    None
+    Some(Board::from(input).solve_cube())
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 22);
+    let input = &advent_of_code::read_input_or_stdin(22);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
+
+    if let (true, Ok(input)) = (verbose(), input) {
+        let mut connections: Vec<_> = edge_connections(input).into_iter().collect();
+        connections.sort_by_key(|&((face, direction), _)| (face, direction as usize));
+
+        println!("Cube edges:");
+        for ((from, leaving), (to, arriving)) in connections {
+            println!(
+                "  face {} heading {:?} -> face {} heading {:?}",
+                from, leaving, to, arriving
+            );
+        }
+
+        let board = Board::from(input.as_str());
+        board.solve_cube();
+        println!("Path around the cube:\n{}", render_path(&board.grid));
+
+        let (flat, cube) = both_passwords(input);
+        println!(
+            "The same path gives {} on the flat map and {} on the cube",
+            flat, cube
+        );
+    }
 }
 
 #[cfg(test)]
@@ -375,13 +535,143 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 22);
+        let input = advent_of_code::read_example(22).unwrap();
         assert_eq!(part_one(&input), Some(6032));
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 22);
-        assert_eq!(part_two(&input), None);
+        let input = advent_of_code::read_example(22).unwrap();
+        assert_eq!(part_two(&input), Some(5031));
+    }
+
+    #[test]
+    fn test_parse_instructions() {
+        assert_eq!(
+            parse_instructions("10R5L"),
+            Ok(vec![
+                Instruction::Forward(10),
+                Instruction::Clockwise,
+                Instruction::Forward(5),
+                Instruction::Anticlockwise
+            ])
+        );
+        assert_eq!(parse_instructions(""), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_parse_instructions_stray_character() {
+        assert_eq!(
+            parse_instructions("10RX5"),
+            Err(ParseError::new("instruction", "X"))
+        );
+    }
+
+    #[test]
+    fn test_parse_bad_cell() {
+        let err = Cell::try_from('@').unwrap_err();
+        assert!(err.to_string().contains('@'), "{}", err);
+
+        assert_eq!("#".parse::<Cell>(), Ok(Cell::Wall));
+        assert_eq!("..".parse::<Cell>(), Err(ParseError::new("cell", "..")));
+    }
+
+    #[test]
+    fn test_trailing_newline() {
+        let input = advent_of_code::read_example(22).unwrap();
+
+        assert_eq!(parse_instructions("10R5L5\n"), parse_instructions("10R5L5"));
+        assert_eq!(part_one(&format!("{}\n\n", input)), Some(6032));
+        assert_eq!(part_two(&format!("{}  \n", input.trim_end())), Some(5031));
+    }
+
+    const EMPTY_TOP_LEFT: &str = "    #\n  ...\n  .#.\n\n2R1\n";
+
+    #[test]
+    fn test_start_after_leading_nothingness() {
+        let board = Board::from(EMPTY_TOP_LEFT);
+
+        assert_eq!((board.start().x, board.start().y), (2, 1));
+        assert_eq!(board.solve_flat(), 3021);
+    }
+
+    #[test]
+    fn test_grid_covers_map() {
+        let board = Board::from(EMPTY_TOP_LEFT);
+        let rendered = render_path(&board.grid);
+        let rows: Vec<&str> = rendered.lines().collect();
+
+        assert_eq!(board.grid.width(), 5);
+        assert_eq!(board.grid.height(), 3);
+        assert_eq!(rows, vec!["    #", "  ...", "  .#."]);
+    }
+
+    #[test]
+    fn test_both_passwords() {
+        let input = advent_of_code::read_example(22).unwrap();
+        assert_eq!(both_passwords(&input), (6032, 5031));
+    }
+
+    #[test]
+    fn test_board_solves_both_parts() {
+        let input = advent_of_code::read_example(22).unwrap();
+        let board = Board::from(input.as_str());
+
+        assert_eq!(board.solve_flat(), 6032);
+        assert_eq!(board.solve_cube(), 5031);
+        // and again, to check nothing from the previous runs carries over
+        assert_eq!(board.solve_flat(), 6032);
+        assert_eq!(render_path(&board.grid), {
+            let fresh = Board::from(input.as_str());
+            fresh.solve_flat();
+            render_path(&fresh.grid)
+        });
+    }
+
+    #[test]
+    fn test_direction_turn() {
+        use Direction::*;
+
+        for (from, clockwise, anticlockwise) in [
+            (North, East, West),
+            (East, South, North),
+            (South, West, East),
+            (West, North, South),
+        ] {
+            assert_eq!(from.turn(&Instruction::Clockwise), clockwise);
+            assert_eq!(from.turn(&Instruction::Anticlockwise), anticlockwise);
+            assert_eq!(from.turn(&Instruction::Forward(3)), from);
+        }
+    }
+
+    #[test]
+    fn test_render_path() {
+        let input = advent_of_code::read_example(22).unwrap();
+        let board = Board::from(input.as_str());
+        board.solve_flat();
+
+        let rendered = render_path(&board.grid);
+        let lines: Vec<&str> = rendered.lines().collect();
+
+        assert_eq!(lines[0], "        .>>#    ");
+        assert_eq!(lines[1], "        .#⌄.    ");
+        // the final step east
+        assert_eq!(lines[5].chars().nth(7), Some('>'));
+    }
+
+    #[test]
+    fn test_edge_connections() {
+        let input = advent_of_code::read_example(22).unwrap();
+        let connections = edge_connections(&input);
+
+        assert_eq!(connections.len(), 24);
+        // faces which touch on the map
+        assert_eq!(connections[&(0, Direction::South)], (3, Direction::South));
+        assert_eq!(connections[&(3, Direction::North)], (0, Direction::North));
+        // faces which only meet once folded
+        assert_eq!(connections[&(0, Direction::North)], (1, Direction::South));
+        assert_eq!(connections[&(3, Direction::East)], (5, Direction::South));
+        assert_eq!(connections[&(4, Direction::South)], (1, Direction::North));
+        assert_eq!(connections[&(5, Direction::North)], (3, Direction::West));
     }
 }
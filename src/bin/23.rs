@@ -1,13 +1,18 @@
-use itertools::Itertools;
-use std::collections::HashSet;
+use advent_of_code::helpers::{render_colored, verbose, Color, IPoint};
+use std::collections::{HashMap, HashSet};
 
-fn parse(input: &str) -> HashSet<(isize, isize)> {
+type Elves = HashSet<IPoint>;
+
+fn parse(input: &str) -> Elves {
     let mut elves = vec![];
 
     for (y, line) in input.lines().enumerate() {
         for (x, ch) in line.chars().enumerate() {
             if ch == '#' {
-                elves.push((x as isize, y as isize));
+                elves.push(IPoint {
+                    x: x as isize,
+                    y: y as isize,
+                });
             }
         }
     }
@@ -20,35 +25,38 @@ fn parse(input: &str) -> HashSet<(isize, isize)> {
     positions
 }
 
-fn print_grid(positions: &HashSet<(isize, isize)>) -> (String, (isize, isize), (isize, isize)) {
-    let (from, to) = {
-        let (min_x, max_x, min_y, max_y) = positions.iter().fold(
-            (isize::MAX, isize::MIN, isize::MAX, isize::MIN),
-            |(mut min_x, mut max_x, mut min_y, mut max_y), (x, y)| {
-                if *x < min_x {
-                    min_x = *x;
-                }
-                if *x > max_x {
-                    max_x = *x;
-                }
-                if *y < min_y {
-                    min_y = *y;
-                }
-                if *y > max_y {
-                    max_y = *y;
-                }
+// Returns the top left and bottom right corners of the smallest rectangle containing every elf.
+fn bounding_box(positions: &Elves) -> (IPoint, IPoint) {
+    let (min_x, max_x, min_y, max_y) = positions.iter().fold(
+        (isize::MAX, isize::MIN, isize::MAX, isize::MIN),
+        |(mut min_x, mut max_x, mut min_y, mut max_y), IPoint { x, y }| {
+            if *x < min_x {
+                min_x = *x;
+            }
+            if *x > max_x {
+                max_x = *x;
+            }
+            if *y < min_y {
+                min_y = *y;
+            }
+            if *y > max_y {
+                max_y = *y;
+            }
 
-                (min_x, max_x, min_y, max_y)
-            },
-        );
+            (min_x, max_x, min_y, max_y)
+        },
+    );
 
-        ((min_x, min_y), (max_x, max_y))
-    };
+    (IPoint { x: min_x, y: min_y }, IPoint { x: max_x, y: max_y })
+}
+
+fn print_grid(positions: &Elves) -> (String, IPoint, IPoint) {
+    let (from, to) = bounding_box(positions);
 
     let mut s = String::new();
-    for row in from.1..=to.1 {
-        for col in from.0..=to.0 {
-            if positions.contains(&(col, row)) {
+    for y in from.y..=to.y {
+        for x in from.x..=to.x {
+            if positions.contains(&IPoint { x, y }) {
                 s.push('#');
             } else {
                 s.push('.');
@@ -61,43 +69,94 @@ fn print_grid(positions: &HashSet<(isize, isize)>) -> (String, (isize, isize), (
     (s, from, to)
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Direction {
+    North,
+    South,
+    West,
+    East,
+}
+
+impl Direction {
+    // The positions an elf checks are empty before proposing to move in this direction. The first
+    // is where it proposes to move to.
+    pub fn candidates(self, elf: IPoint) -> [IPoint; 3] {
+        let offsets = match self {
+            Self::North => [(0, -1), (1, -1), (-1, -1)],
+            Self::South => [(0, 1), (1, 1), (-1, 1)],
+            Self::West => [(-1, 0), (-1, -1), (-1, 1)],
+            Self::East => [(1, 0), (1, -1), (1, 1)],
+        };
+
+        offsets.map(|(x, y)| elf + IPoint { x, y })
+    }
+}
+
+// The order in which the puzzle has the elves consider directions on the first round. Each round
+// starts from the next direction in the list.
+const PUZZLE_ORDER: [Direction; 4] = [
+    Direction::North,
+    Direction::South,
+    Direction::West,
+    Direction::East,
+];
+
+// The working a round needs besides the elves' positions, kept from one round to the next so that
+// it's only allocated once.
+#[derive(Debug, Default)]
+struct Scratch {
+    moves: Vec<(IPoint, IPoint)>, // each elf which wants to move, and where it proposes to go
+    proposals: HashMap<IPoint, usize>, // how many elves propose moving to each position
+}
+
+impl Scratch {
+    // Room enough for every one of the given number of elves to propose a move.
+    fn with_capacity(elves: usize) -> Self {
+        Scratch {
+            moves: Vec::with_capacity(elves),
+            proposals: HashMap::with_capacity(elves),
+        }
+    }
+}
+
 fn play_game(
-    mut positions: HashSet<(isize, isize)>,
+    positions: &Elves,
+    next: &mut Elves,
+    scratch: &mut Scratch,
+    round_identifier: usize,
+) -> usize {
+    play_game_with_order(positions, next, scratch, round_identifier, &PUZZLE_ORDER)
+}
+
+// Plays a round of the game, writing the elves' positions at the end of the round into `next`. The
+// caller can then swap the two sets for the next round, so both buffers are reused throughout the
+// game rather than building a new set each round, as is the scratch space. Elves consider the
+// directions in `order`, rotated by the round number. Returns how many elves moved.
+fn play_game_with_order(
+    positions: &Elves,
+    next: &mut Elves,
+    scratch: &mut Scratch,
     round_identifier: usize,
-) -> (HashSet<(isize, isize)>, usize) {
-    let mut moves = vec![];
-    let starting_elves = positions.len();
-
-    for (x, y) in positions.iter() {
-        if vec![
-            (*x - 1, *y - 1),
-            (*x, *y - 1),
-            (*x + 1, *y - 1),
-            (*x - 1, *y),
-            (*x + 1, *y),
-            (*x - 1, *y + 1),
-            (*x, *y + 1),
-            (*x + 1, *y + 1),
-        ]
-        .iter()
-        .filter(|&adj| positions.contains(adj))
-        .count()
-            == 0
-        {
+    order: &[Direction; 4],
+) -> usize {
+    let Scratch { moves, proposals } = scratch;
+    moves.clear();
+    proposals.clear();
+    next.clear();
+
+    let mut order = *order;
+    order.rotate_left(round_identifier % 4);
+
+    for &elf in positions.iter() {
+        if !elf.neighbours8().iter().any(|adj| positions.contains(adj)) {
             // This elf has no adjacents so does not move on this round.
+            next.insert(elf);
             continue;
         }
 
-        let mut candidates = vec![
-            vec![(*x, *y - 1), (*x + 1, *y - 1), (*x - 1, *y - 1)], // North
-            vec![(*x, *y + 1), (*x + 1, *y + 1), (*x - 1, *y + 1)], // South
-            vec![(*x - 1, *y), (*x - 1, *y - 1), (*x - 1, *y + 1)], // West
-            vec![(*x + 1, *y), (*x + 1, *y - 1), (*x + 1, *y + 1)], // East
-        ];
-        candidates.rotate_left(round_identifier % 4);
-
-        let (next_x, next_y) = candidates
+        let proposed = order
             .iter()
+            .map(|direction| direction.candidates(elf))
             .find_map(|candidates| {
                 if candidates.iter().all(|c| !positions.contains(c)) {
                     Some(*candidates.first().unwrap())
@@ -105,73 +164,140 @@ fn play_game(
                     None
                 }
             })
-            .unwrap_or((*x, *y));
+            .unwrap_or(elf);
+
+        moves.push((elf, proposed));
+    }
 
-        moves.push(((*x, *y), (next_x, next_y)));
+    for (_, to) in moves.iter() {
+        *proposals.entry(*to).or_default() += 1;
     }
 
-    let move_count = moves.iter().counts_by(|(_, to)| *to);
+    let mut moved = 0;
 
-    for (from, to) in &moves {
-        if move_count[to] == 1 && !positions.contains(&to) {
+    for (from, to) in moves.iter() {
+        if proposals[to] == 1 && !positions.contains(to) {
             // moves as nobody else proposed to move here
-            positions.remove(&from);
-            positions.insert(*to);
+            next.insert(*to);
+            moved += 1;
+        } else {
+            next.insert(*from);
         }
     }
 
-    assert!(starting_elves == positions.len());
+    assert!(positions.len() == next.len());
 
-    (
-        positions,
-        move_count.iter().filter(|(_, &count)| count == 1).count(),
-    )
+    moved
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+// Counts the empty ground tiles in the smallest rectangle containing every elf after the given
+// number of rounds.
+fn empty_ground(input: &str, rounds: usize) -> u32 {
     let mut positions = parse(input);
+    let mut next = HashSet::with_capacity(positions.len());
+    let mut scratch = Scratch::with_capacity(positions.len());
 
-    for step in 0..10 {
-        let (grid, _, _) = print_grid(&positions);
-        println!("===============\nBefore step {}\n\n{}\n", step, grid);
+    for step in 0..rounds {
+        play_game(&positions, &mut next, &mut scratch, step);
+        std::mem::swap(&mut positions, &mut next);
+    }
 
-        (positions, _) = play_game(positions, step);
+    let (from, to) = bounding_box(&positions);
+    let empty_squares =
+        ((from.y.abs_diff(to.y) + 1) * (from.x.abs_diff(to.x) + 1)) - positions.len();
+
+    empty_squares as u32
+}
+
+// Renders the elves before the first round and after each of the given number of rounds.
+fn frames(input: &str, rounds: usize) -> Vec<String> {
+    let mut positions = parse(input);
+    let mut next = HashSet::with_capacity(positions.len());
+    let mut scratch = Scratch::with_capacity(positions.len());
+    let mut frames = vec![print_grid(&positions).0];
+
+    for round in 0..rounds {
+        play_game(&positions, &mut next, &mut scratch, round);
+        std::mem::swap(&mut positions, &mut next);
+        frames.push(print_grid(&positions).0);
     }
 
-    let (grid, from, to) = print_grid(&positions);
-    println!("{}", grid);
+    frames
+}
 
-    let empty_squares =
-        ((from.1.abs_diff(to.1) + 1) * (from.0.abs_diff(to.0) + 1)) - positions.len();
+// Prints the frames of the given number of rounds, for following the elves when AOC_VERBOSE is set.
+fn print_frames(input: &str, rounds: usize) {
+    for (step, frame) in frames(input, rounds).iter().enumerate() {
+        let grid = render_colored(frame, &[('#', Color::Green)]);
+        println!("===============\nBefore step {}\n\n{}\n", step, grid);
+    }
+}
 
-    Some(empty_squares as u32)
+pub fn part_one(input: &str) -> Option<u32> {
+    Some(empty_ground(input, 10))
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+// Returns how many elves moved in each round, up to and including the first round in which no elf
+// moves, or until `max_rounds` have been played.
+fn move_counts(input: &str, max_rounds: usize) -> Vec<usize> {
     let mut elves = parse(input);
-    let mut rounds = 0;
-
-    loop {
-        let (grid, _, _) = print_grid(&elves);
-        println!("===============\nBefore step {}\n\n{}\n", rounds, grid);
+    let mut next = HashSet::with_capacity(elves.len());
+    let mut scratch = Scratch::with_capacity(elves.len());
+    let mut counts = vec![];
 
-        let (new_elves, moved) = play_game(elves, rounds);
+    for round in 0..max_rounds {
+        let moved = play_game(&elves, &mut next, &mut scratch, round);
+        counts.push(moved);
 
         if moved == 0 {
             break;
-        } else {
-            elves = new_elves;
         }
 
-        rounds += 1;
+        std::mem::swap(&mut elves, &mut next);
     }
 
-    Some(rounds as u32 + 1)
+    counts
+}
+
+// Real inputs settle in around a thousand rounds, so this leaves plenty of headroom while making
+// sure an input that never settles can't keep us going forever.
+const MAX_ROUNDS: usize = 10_000;
+
+// Returns the first round in which no elf moves, or None if that doesn't happen within
+// `max_rounds`.
+fn rounds_to_settle(input: &str, max_rounds: usize) -> Option<usize> {
+    let counts = move_counts(input, max_rounds);
+
+    if counts.last() == Some(&0) {
+        Some(counts.len())
+    } else {
+        None
+    }
+}
+
+// Not settling is a failure rather than a part left to do, so it mustn't come back as None, which
+// solve! reports as not yet implemented.
+pub fn part_two(input: &str) -> Option<u32> {
+    let rounds = rounds_to_settle(input, MAX_ROUNDS)
+        .unwrap_or_else(|| panic!("the elves did not settle within {} rounds", MAX_ROUNDS));
+
+    Some(rounds as u32)
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 23);
+    let input = &advent_of_code::read_input_or_stdin(23);
+
+    if let (true, Ok(input)) = (verbose(), input) {
+        print_frames(input, 10);
+    }
     advent_of_code::solve!(1, part_one, input);
+
+    if let (true, Ok(input)) = (verbose(), input) {
+        print_frames(
+            input,
+            rounds_to_settle(input, MAX_ROUNDS).unwrap_or(MAX_ROUNDS),
+        );
+    }
     advent_of_code::solve!(2, part_two, input);
 }
 
@@ -181,13 +307,179 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 23);
+        let input = advent_of_code::read_example(23).unwrap();
         assert_eq!(part_one(&input), Some(110));
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 23);
+        let input = advent_of_code::read_example(23).unwrap();
         assert_eq!(part_two(&input), Some(20));
     }
+
+    #[test]
+    fn test_empty_ground() {
+        let input = advent_of_code::read_example(23).unwrap();
+
+        // the example starts with 22 elves in a 7x7 square
+        assert_eq!(empty_ground(&input, 0), 27);
+        assert_eq!(empty_ground(&input, 10), 110);
+    }
+
+    #[test]
+    fn test_moved_excludes_conflicts() {
+        // The middle and bottom left elves both propose moving to (2, 3), so neither moves.
+        let positions = parse(".....\n..##.\n..#..\n.....\n..##.\n.....");
+        let mut next = HashSet::new();
+
+        assert_eq!(
+            play_game(&positions, &mut next, &mut Scratch::default(), 0),
+            3
+        );
+        assert!(next.contains(&IPoint { x: 2, y: 2 }) && next.contains(&IPoint { x: 2, y: 4 }));
+
+        // The elf in the middle of a block can't propose anywhere, so stays put.
+        let positions = parse("###\n###\n###");
+        let moved = play_game(&positions, &mut next, &mut Scratch::default(), 0);
+
+        assert_eq!(moved, positions.difference(&next).count());
+        assert!(next.contains(&IPoint { x: 1, y: 1 }));
+    }
+
+    #[test]
+    fn test_frames() {
+        let frames = frames(".....\n..##.\n..#..\n.....\n..##.\n.....", 3);
+
+        assert_eq!(frames.len(), 4);
+        assert_eq!(frames[0], "##\n#.\n..\n##\n");
+        assert_eq!(frames[1], "##\n..\n#.\n.#\n#.\n");
+        assert_eq!(frames[3], "..#..\n....#\n#....\n....#\n.....\n..#..\n");
+    }
+
+    #[test]
+    fn test_negative_coordinates() {
+        // both elves start on row 0, so step into row -1 then split left into column -1
+        assert_eq!(frames("##", 3), vec!["##\n", "##\n", "##\n", "#..#\n"]);
+        assert_eq!(empty_ground("##", 1), 0);
+        assert_eq!(empty_ground("##", 3), 2);
+
+        let input = "###\n###\n###";
+        let mut positions = parse(input);
+        let mut next = HashSet::new();
+        let mut scratch = Scratch::default();
+
+        for round in 0..5 {
+            play_game(&positions, &mut next, &mut scratch, round);
+            std::mem::swap(&mut positions, &mut next);
+        }
+
+        let (grid, from, to) = print_grid(&positions);
+        let rows: Vec<&str> = grid.lines().collect();
+
+        assert!(from.x < 0 && from.y < 0);
+        assert_eq!(rows.len(), from.y.abs_diff(to.y) + 1);
+        assert!(rows
+            .iter()
+            .all(|row| row.len() == from.x.abs_diff(to.x) + 1));
+        assert_eq!(grid.matches('#').count(), 9);
+        assert!(positions
+            .iter()
+            .all(
+                |&IPoint { x, y }| rows[(y - from.y) as usize].as_bytes()[(x - from.x) as usize]
+                    == b'#'
+            ));
+        assert_eq!(empty_ground(input, 5) as usize, grid.matches('.').count());
+    }
+
+    #[test]
+    fn test_move_counts() {
+        let input = advent_of_code::read_example(23).unwrap();
+        let counts = move_counts(&input, 100);
+
+        assert_eq!(counts.len(), 20);
+        assert_eq!(counts[0], 11);
+        assert_eq!(counts.last(), Some(&0));
+        assert_eq!(move_counts(&input, 3).len(), 3);
+    }
+
+    #[test]
+    fn test_rounds_to_settle() {
+        let input = advent_of_code::read_example(23).unwrap();
+
+        assert_eq!(rounds_to_settle(&input, 20), Some(20));
+        assert_eq!(rounds_to_settle(&input, 19), None);
+        assert_eq!(rounds_to_settle(&input, 0), None);
+    }
+
+    #[test]
+    fn test_direction_order() {
+        let settle = |order: &[Direction; 4]| {
+            let mut positions = parse("##");
+            let mut next = HashSet::new();
+            let mut scratch = Scratch::default();
+            let mut states = vec![];
+
+            for round in 0..100 {
+                if play_game_with_order(&positions, &mut next, &mut scratch, round, order) == 0 {
+                    let (from, to) = bounding_box(&positions);
+                    let empty =
+                        (from.x.abs_diff(to.x) + 1) * (from.y.abs_diff(to.y) + 1) - positions.len();
+
+                    return (states, empty);
+                }
+
+                std::mem::swap(&mut positions, &mut next);
+                states.push(positions.clone());
+            }
+
+            panic!("elves never settled");
+        };
+
+        // Under the puzzle's order the pair step north, back south, then apart. Considering west
+        // and east first splits them up straight away. Either way they end up with a gap between
+        // them, although in general a different order can settle into a different shape.
+        let (puzzle_states, puzzle_empty) = settle(&PUZZLE_ORDER);
+        let (other_states, other_empty) = settle(&[
+            Direction::West,
+            Direction::East,
+            Direction::North,
+            Direction::South,
+        ]);
+
+        assert_eq!(puzzle_states.len(), 3);
+        assert_eq!(other_states.len(), 1);
+        assert_ne!(puzzle_states[0], other_states[0]);
+        assert_eq!(puzzle_empty, 2);
+        assert_eq!(other_empty, puzzle_empty);
+    }
+
+    #[test]
+    fn test_rounds_reuse_buffers() {
+        let input = advent_of_code::read_example(23).unwrap();
+        let mut positions = parse(&input);
+        let mut next = HashSet::with_capacity(positions.len());
+        let mut scratch = Scratch::with_capacity(positions.len());
+        let mut reallocations = 0;
+
+        for round in 0..1000 {
+            let capacities = |positions: &Elves, next: &Elves, scratch: &Scratch| {
+                (
+                    positions.capacity(),
+                    next.capacity(),
+                    scratch.moves.capacity(),
+                    scratch.proposals.capacity(),
+                )
+            };
+            let before = capacities(&positions, &next, &scratch);
+
+            play_game(&positions, &mut next, &mut scratch, round);
+            std::mem::swap(&mut positions, &mut next);
+
+            if capacities(&next, &positions, &scratch) != before {
+                reallocations += 1;
+            }
+        }
+
+        assert_eq!(reallocations, 0);
+    }
 }
@@ -1,6 +1,10 @@
-use std::{collections::HashMap, fmt::Display};
+use advent_of_code::helpers::{bfs, render_colored, verbose, Color, ParseError};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+};
 
-#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
 enum BlizzardDirection {
     Up,
     Right,
@@ -8,19 +12,8 @@ enum BlizzardDirection {
     Left,
 }
 
-#[derive(Debug)]
-struct BlizzardDirectionParseError {}
-
-impl Display for BlizzardDirectionParseError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "error parsing blizzard direction")
-    }
-}
-
-impl std::error::Error for BlizzardDirectionParseError {}
-
 impl TryFrom<char> for BlizzardDirection {
-    type Error = BlizzardDirectionParseError;
+    type Error = ParseError;
 
     fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
@@ -28,7 +21,7 @@ impl TryFrom<char> for BlizzardDirection {
             '>' => Ok(Self::Right),
             '^' => Ok(Self::Up),
             'v' => Ok(Self::Down),
-            _ => Err(Self::Error {}),
+            _ => Err(ParseError::new("blizzard direction", value)),
         }
     }
 }
@@ -48,42 +41,154 @@ impl Display for BlizzardDirection {
     }
 }
 
-fn parse(
-    input: &str,
-) -> (
-    HashMap<(isize, isize), Vec<BlizzardDirection>>,
-    (usize, usize),
-) {
+type Blizzards = HashMap<(isize, isize), Vec<BlizzardDirection>>;
+
+#[derive(Debug)]
+struct ValleyParseError {
+    reason: String,
+}
+
+impl Display for ValleyParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "error parsing valley: {}", self.reason)
+    }
+}
+
+impl std::error::Error for ValleyParseError {}
+
+fn parse(input: &str) -> Result<(Blizzards, (usize, usize)), ValleyParseError> {
+    let error = |reason: String| ValleyParseError { reason };
+
+    let lines: Vec<&str> = input.lines().filter(|l| !l.trim().is_empty()).collect();
+
+    if lines.len() < 3 {
+        return Err(error(
+            "expected walls above and below the valley".to_string(),
+        ));
+    }
+
+    let (top, rows, bottom) = (lines[0], &lines[1..lines.len() - 1], lines[lines.len() - 1]);
+    let width = top.len();
+
+    if width < 3 {
+        return Err(error(format!("top wall {:?} is too narrow", top)));
+    }
+
+    // The solver models the entrance as the top left and the exit as the bottom right of the
+    // valley, so insist the walls actually look like that rather than giving the wrong answer.
+    let gaps = |wall: &str| -> Vec<usize> {
+        wall.char_indices()
+            .filter(|&(_, ch)| ch != '#')
+            .map(|(i, _)| i)
+            .collect()
+    };
+
+    if gaps(top) != vec![1] {
+        return Err(error(format!(
+            "top wall {:?} must have a single gap at the left",
+            top
+        )));
+    }
+
+    if bottom.len() != width || gaps(bottom) != vec![width - 2] {
+        return Err(error(format!(
+            "bottom wall {:?} must have a single gap at the right",
+            bottom
+        )));
+    }
+
     let mut result = HashMap::new();
-    let (mut width, mut height) = (0, 0);
-
-    for (row, line) in input
-        .lines()
-        .skip(1)
-        .enumerate()
-        .take_while(|(_, l)| !l.starts_with("##"))
-    {
-        for (col, ch) in line.chars().skip(1).enumerate() {
-            if ch == '#' {
-                break;
-            } else if ch == '.' {
+
+    for (row, line) in rows.iter().enumerate() {
+        if line.len() != width || !line.starts_with('#') || !line.ends_with('#') {
+            return Err(error(format!(
+                "row {:?} must be {} wide and bounded by walls",
+                line, width
+            )));
+        }
+
+        for (col, ch) in line[1..width - 1].chars().enumerate() {
+            if ch == '.' {
                 continue;
-            } else {
-                result.insert((col as isize, row as isize), vec![ch.try_into().unwrap()]);
             }
 
-            width = col + 1;
+            let direction = BlizzardDirection::try_from(ch)
+                .map_err(|err| error(format!("{} in row {:?}", err, line)))?;
+
+            result.insert((col as isize, row as isize), vec![direction]);
+        }
+    }
+
+    Ok((result, (width - 2, rows.len())))
+}
+
+// Draws the valley as it appears in the puzzle input, with a count where blizzards share a cell.
+fn render(blizzards: &Blizzards, (width, height): (usize, usize)) -> String {
+    let mut lines = vec![format!("#.{}", "#".repeat(width))];
+
+    for row in 0..height as isize {
+        let mut line = String::from("#");
+
+        for col in 0..width as isize {
+            match blizzards.get(&(col, row)).map(Vec::as_slice) {
+                None | Some([]) => line.push('.'),
+                Some([blizzard]) => line.push_str(&blizzard.to_string()),
+                Some(several) => line.push_str(&several.len().to_string()),
+            }
+        }
+
+        line.push('#');
+        lines.push(line);
+    }
+
+    lines.push(format!("{}.#", "#".repeat(width)));
+    lines.join("\n")
+}
+
+// Moves every blizzard one step, wrapping around to the other side of the valley when it reaches a
+// wall. Blizzards never pass through the entrances, as no real input has blizzards heading up or
+// down in those columns.
+fn step_blizzards(blizzards: &Blizzards, (width, height): (usize, usize)) -> Blizzards {
+    let (width, height) = (width as isize, height as isize);
+    let mut next: Blizzards = HashMap::new();
+
+    for (&(x, y), directions) in blizzards {
+        for &direction in directions {
+            let (x, y) = match direction {
+                BlizzardDirection::Up => (x, y - 1),
+                BlizzardDirection::Down => (x, y + 1),
+                BlizzardDirection::Left => (x - 1, y),
+                BlizzardDirection::Right => (x + 1, y),
+            };
+
+            next.entry((x.rem_euclid(width), y.rem_euclid(height)))
+                .or_default()
+                .push(direction);
         }
+    }
+
+    next
+}
 
-        height = row + 1;
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
     }
+}
 
-    (result, (width, height))
+fn lcm(a: usize, b: usize) -> usize {
+    a / gcd(a, b) * b
 }
 
 struct Puzzle {
-    blizzards: HashMap<(isize, isize), Vec<BlizzardDirection>>,
+    blizzards: Blizzards,       // positions at minute 0
     dimensions: (usize, usize), // width x height
+    minute: usize,              // the clock keeps ticking between trips through the valley
+    // The positions occupied by blizzards at each minute of their cycle, which repeats every
+    // lcm(width, height) minutes.
+    occupancy: Vec<HashSet<(isize, isize)>>,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -102,90 +207,144 @@ impl ValleyPortal {
 }
 
 impl Puzzle {
-    pub fn step_blizzards(&mut self) {
-        // Update positions of all blizzards
-        self.blizzards = {
-            let mut next = HashMap::new();
-            fn resolve_dimension(cur: isize, max: isize) -> isize {
-                if cur < 0 {
-                    max - 1
-                } else if cur >= max {
-                    0
-                } else {
-                    cur
-                }
-            }
+    pub fn new(blizzards: Blizzards, dimensions: (usize, usize)) -> Puzzle {
+        let mut puzzle = Puzzle {
+            blizzards,
+            dimensions,
+            minute: 0,
+            occupancy: vec![],
+        };
 
-            let resolve = |(x, y)| {
-                // blizzards in part 2 need to account for going to the entrances and exits
-                // :scream:
-                if (x == 0 && y == -1)
-                    || (x == self.dimensions.0 as isize - 1 && y == self.dimensions.1 as isize)
-                {
-                    (x, y)
-                } else if x == 0 && y == self.dimensions.1 as isize {
-                    // The blizzard at the left will start from the entrance, not y = 0 (we don't
-                    // need to special case the exit blizzard wrapping as that will just start
-                    // again at y = 0 in the else case.
-                    (0, -1)
-                } else {
-                    (
-                        resolve_dimension(x, self.dimensions.0 as isize),
-                        resolve_dimension(y, self.dimensions.1 as isize),
-                    )
-                }
-            };
+        let mut blizzards = puzzle.blizzards.clone();
 
-            for ((x, y), directions) in self.blizzards.clone() {
-                for direction in directions {
-                    next.entry(resolve(match direction {
-                        BlizzardDirection::Up => (x, y - 1),
-                        BlizzardDirection::Down => (x, y + 1),
-                        BlizzardDirection::Left => (x - 1, y),
-                        BlizzardDirection::Right => (x + 1, y),
-                    }))
-                    .or_insert(vec![])
-                    .push(direction);
-                }
+        for _ in 0..lcm(dimensions.0, dimensions.1) {
+            puzzle.occupancy.push(blizzards.keys().copied().collect());
+            blizzards = step_blizzards(&blizzards, dimensions);
+        }
+
+        puzzle
+    }
+
+    fn portal(&self, portal: ValleyPortal) -> (isize, isize) {
+        // The valley entrances are modelled at (0,-1) and (width-1, height)
+        match portal {
+            ValleyPortal::TopLeft => (0, -1),
+            ValleyPortal::BottomRight => {
+                (self.dimensions.0 as isize - 1, self.dimensions.1 as isize)
             }
+        }
+    }
 
-            next
-        };
+    fn in_valley(&self, (x, y): (isize, isize)) -> bool {
+        let (width, height) = (self.dimensions.0 as isize, self.dimensions.1 as isize);
+
+        (0..width).contains(&x) && (0..height).contains(&y)
+            || (x, y) == self.portal(ValleyPortal::TopLeft)
+            || (x, y) == self.portal(ValleyPortal::BottomRight)
+    }
+
+    fn is_occupied(&self, position: (isize, isize), minute: usize) -> bool {
+        self.occupancy[minute % self.occupancy.len()].contains(&position)
     }
 
     pub fn solve(&mut self, start: ValleyPortal) -> Option<u32> {
+        self.solve_with_path(start).map(|(minutes, _)| minutes)
+    }
+
+    // Breadth first search over the positions we could be in at each minute. The blizzards repeat
+    // every lcm(width, height) minutes, so it's enough to track where we are in that cycle: being
+    // at the same position at the same point in the cycle as some earlier minute can't get us out
+    // any quicker and need not be explored again.
+    //
+    // Alongside the minutes taken, returns every position the expedition occupied on the way,
+    // from the starting portal to the one opposite, one per minute.
+    pub fn solve_with_path(&mut self, start: ValleyPortal) -> Option<(u32, Vec<(isize, isize)>)> {
+        let period = self.occupancy.len();
+        let (from, to) = (self.portal(start), self.portal(start.other()));
+        let puzzle = &*self;
+
+        let (steps, path) = bfs(
+            (from, self.minute % period),
+            |&((x, y), minute)| {
+                let minute = (minute + 1) % period;
+                let candidates = [
+                    (x, y),     // shelter in place
+                    (x - 1, y), // move left
+                    (x + 1, y), // move right
+                    (x, y - 1), // move up
+                    (x, y + 1), // move down
+                ];
+
+                candidates
+                    .into_iter()
+                    .filter(move |&candidate| {
+                        puzzle.in_valley(candidate) && !puzzle.is_occupied(candidate, minute)
+                    })
+                    .map(move |candidate| (candidate, minute))
+            },
+            |&(position, _)| position == to,
+        )?;
+
+        self.minute += steps;
+
+        Some((
+            steps as u32,
+            path.into_iter().map(|(position, _)| position).collect(),
+        ))
+    }
+
+    // The longest a crossing can take, if it can be made at all. A quickest route never visits the
+    // same position at the same point in the blizzards' cycle twice, so it can't take longer than
+    // there are such states.
+    fn max_crossing_minutes(&self) -> usize {
+        let (width, height) = self.dimensions;
+
+        (width * height + 2) * self.occupancy.len()
+    }
+
+    // The original solution, which tracks every minute at which each position was reachable. It is
+    // much slower and its memory grows with every minute, but it's handy to check against. It gives
+    // up if the far portal hasn't been reached after max_minutes.
+    pub fn solve_by_reachability(
+        &mut self,
+        start: ValleyPortal,
+        max_minutes: usize,
+    ) -> Option<u32> {
         let mut reachability: HashMap<(isize, isize), Vec<usize>> = HashMap::new();
         let (width, height) = self.dimensions;
 
+        let mut blizzards = self.blizzards.clone();
+        for _ in 0..self.minute {
+            blizzards = step_blizzards(&blizzards, self.dimensions);
+        }
+
         // We can reach the starting location at step 0.
-        reachability.insert(
-            match start {
-                ValleyPortal::TopLeft => (0, -1),
-                ValleyPortal::BottomRight => (width as isize - 1, height as isize),
-            },
-            vec![0],
-        );
+        reachability.insert(self.portal(start), vec![0]);
 
         let mut steps = 0;
 
         loop {
-            eprintln!("solve has stepped {} times", steps);
+            if steps == max_minutes {
+                return None;
+            }
+
+            if verbose() {
+                eprintln!("solve has stepped {} times", steps);
+            }
             steps += 1;
 
-            self.step_blizzards();
+            blizzards = step_blizzards(&blizzards, self.dimensions);
 
             // Update reachability for all positions that we could reach in the last position and which
             // do not currently have a blizzard occupying them or the adjacent.
             for row in -1..height as isize + 1 {
                 for col in 0..width as isize {
-                    let (row, col) = (row as isize, col as isize);
-
-                    if self.blizzards.entry((col, row)).or_default().len() > 0 {
+                    if !blizzards.entry((col, row)).or_default().is_empty() {
                         // there's a blizzard here, we can't stay
                         continue;
                     }
 
-                    let candidates = vec![
+                    let candidates = [
                         (col, row),     // shelter in place
                         (col - 1, row), // move left
                         (col + 1, row), // move right
@@ -196,16 +355,7 @@ impl Puzzle {
                     let is_reachable = candidates
                         .iter()
                         // remove points outside the grid that cannot be accessed
-                        .filter(|(x, y)| {
-                            // The only position permitted outside the grid is the valley entrances
-                            // and exists, which are modelled at (0,-1) and (width-1, height)
-                            (*x == 0 && *y == -1) // start
-                                    || (*x == width as isize - 1 && *y == height as isize) // end
-                                    || (*x >= 0
-                                        && *x < width as isize
-                                        && *y >= 0
-                                        && *y < height as isize)
-                        })
+                        .filter(|&&candidate| self.in_valley(candidate))
                         // to access the point we have to have visited at least one of the adjacent
                         // candidates in the previous round or be at the current point and have
                         // stayed here (assuming we can)
@@ -222,69 +372,84 @@ impl Puzzle {
                 }
             }
 
-            // Check if the end was reached
-            if reachability
-                .entry(
-                    // We're going to the opposite side to where we started.
-                    match start {
-                        ValleyPortal::TopLeft => (width as isize - 1, height as isize),
-                        ValleyPortal::BottomRight => (0, -1),
-                    },
-                )
+            // Check if the end was reached, which is on the opposite side to where we started.
+            if !reachability
+                .entry(self.portal(start.other()))
                 .or_default()
-                .len()
-                > 0
+                .is_empty()
             {
                 break;
             }
         }
 
+        self.minute += steps;
+
         Some(steps as u32)
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let (blizzards, (width, height)) = parse(input);
-
-    let result = Puzzle {
-        blizzards,
-        dimensions: (width, height),
-    }
-    .solve(ValleyPortal::TopLeft);
+// Crosses the valley back and forth for the given number of trips, alternating between the two
+// portals, and returns the total time taken, or None if one of the crossings can't be made.
+pub fn total_time(input: &str, trips: usize) -> Option<u32> {
+    cross_valley(input, trips, Puzzle::solve)
+}
 
-    Some(result.unwrap())
+// As total_time, but crossing with the reference solver. It keeps going until a crossing couldn't
+// possibly take any longer, so beware that a valley which can't be crossed is slow to give up on.
+pub fn total_time_by_reachability(input: &str, trips: usize) -> Option<u32> {
+    cross_valley(input, trips, |puzzle, start| {
+        puzzle.solve_by_reachability(start, puzzle.max_crossing_minutes())
+    })
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let (blizzards, dimensions) = parse(input);
+fn cross_valley(
+    input: &str,
+    trips: usize,
+    solve: fn(&mut Puzzle, ValleyPortal) -> Option<u32>,
+) -> Option<u32> {
+    let (blizzards, dimensions) = parse(input).expect("parsing valley");
+
+    if verbose() {
+        let legend: Vec<(char, Color)> =
+            "<>^v23456789".chars().map(|ch| (ch, Color::Cyan)).collect();
+        println!(
+            "{}\n",
+            render_colored(&render(&blizzards, dimensions), &legend)
+        );
+    }
 
     let mut steps = 0;
-    let mut puzzle = Puzzle {
-        blizzards,
-        dimensions,
-    };
+    let mut puzzle = Puzzle::new(blizzards, dimensions);
 
     let mut start = ValleyPortal::TopLeft;
 
-    // 1. there
-    // 2. back
-    // 3. there again
-    for step in 0..3 {
-        let this_pass = puzzle.solve(start).unwrap();
+    for trip in 0..trips {
+        let this_pass = solve(&mut puzzle, start)?;
         steps += this_pass;
         start = start.other();
 
-        println!(
-            "solved step {} in {} minutes (for {} total)",
-            step, this_pass, steps
-        );
+        if verbose() {
+            println!(
+                "solved step {} in {} minutes (for {} total)",
+                trip, this_pass, steps
+            );
+        }
     }
 
     Some(steps)
 }
 
+pub fn part_one(input: &str) -> Option<u32> {
+    total_time(input, 1)
+}
+
+pub fn part_two(input: &str) -> Option<u32> {
+    // there, back, and there again
+    total_time(input, 3)
+}
+
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 24);
+    let input = &advent_of_code::read_input_or_stdin(24);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
@@ -295,13 +460,223 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 24);
+        let input = advent_of_code::read_example(24).unwrap();
         assert_eq!(part_one(&input), Some(18));
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 24);
+        let input = advent_of_code::read_example(24).unwrap();
         assert_eq!(part_two(&input), Some(54));
     }
+
+    fn step_one(position: (isize, isize), direction: BlizzardDirection) -> Blizzards {
+        step_blizzards(&HashMap::from([(position, vec![direction])]), (6, 4))
+    }
+
+    #[test]
+    fn test_step_blizzards_moves() {
+        use BlizzardDirection::*;
+
+        assert_eq!(step_one((2, 1), Up), HashMap::from([((2, 0), vec![Up])]));
+        assert_eq!(
+            step_one((2, 1), Down),
+            HashMap::from([((2, 2), vec![Down])])
+        );
+        assert_eq!(
+            step_one((2, 1), Left),
+            HashMap::from([((1, 1), vec![Left])])
+        );
+        assert_eq!(
+            step_one((2, 1), Right),
+            HashMap::from([((3, 1), vec![Right])])
+        );
+    }
+
+    #[test]
+    fn test_step_blizzards_wraps() {
+        use BlizzardDirection::*;
+
+        assert_eq!(step_one((2, 0), Up), HashMap::from([((2, 3), vec![Up])]));
+        assert_eq!(
+            step_one((2, 3), Down),
+            HashMap::from([((2, 0), vec![Down])])
+        );
+        assert_eq!(
+            step_one((0, 1), Left),
+            HashMap::from([((5, 1), vec![Left])])
+        );
+        assert_eq!(
+            step_one((5, 1), Right),
+            HashMap::from([((0, 1), vec![Right])])
+        );
+
+        // the corners wrap in the direction of travel only
+        assert_eq!(step_one((0, 0), Up), HashMap::from([((0, 3), vec![Up])]));
+        assert_eq!(
+            step_one((5, 3), Right),
+            HashMap::from([((0, 3), vec![Right])])
+        );
+    }
+
+    #[test]
+    fn test_step_blizzards_share_cells() {
+        use BlizzardDirection::*;
+
+        let next = step_blizzards(
+            &HashMap::from([((0, 1), vec![Right]), ((2, 1), vec![Left])]),
+            (6, 4),
+        );
+
+        assert_eq!(next.len(), 1);
+        assert_eq!(next[&(1, 1)].len(), 2);
+    }
+
+    #[test]
+    fn test_render() {
+        let input = advent_of_code::read_example(24).unwrap();
+        let (blizzards, dimensions) = parse(&input).unwrap();
+
+        assert_eq!(render(&blizzards, dimensions), input.trim_end());
+
+        let after = step_blizzards(&blizzards, dimensions);
+        assert_eq!(render(&after, dimensions).lines().nth(1), Some("#.>3.<.#"));
+    }
+
+    #[test]
+    fn test_total_time() {
+        let input = advent_of_code::read_example(24).unwrap();
+
+        assert_eq!(total_time(&input, 0), Some(0));
+        assert_eq!(total_time(&input, 2), Some(18 + 23));
+    }
+
+    #[test]
+    fn test_solve_with_path() {
+        let input = advent_of_code::read_example(24).unwrap();
+        let (blizzards, dimensions) = parse(&input).unwrap();
+        let mut puzzle = Puzzle::new(blizzards, dimensions);
+
+        let (minutes, path) = puzzle.solve_with_path(ValleyPortal::TopLeft).unwrap();
+
+        assert_eq!(minutes, 18);
+        assert_eq!(path.len(), minutes as usize + 1);
+        assert_eq!(path.first(), Some(&(0, -1)));
+        assert_eq!(path.last(), Some(&(5, 4)));
+
+        // each minute we either stay put or move to one of the adjacent positions, never into a
+        // blizzard
+        for (minute, window) in path.windows(2).enumerate() {
+            let ((x0, y0), (x1, y1)) = (window[0], window[1]);
+
+            assert!((x0 - x1).abs() + (y0 - y1).abs() <= 1);
+            assert!(!puzzle.is_occupied(window[1], minute + 1));
+        }
+    }
+
+    #[test]
+    fn test_parse_dimensions() {
+        let input = advent_of_code::read_example(24).unwrap();
+        let (blizzards, dimensions) = parse(&input).unwrap();
+
+        assert_eq!(dimensions, (6, 4));
+        assert_eq!(blizzards.len(), 19);
+    }
+
+    #[test]
+    fn test_parse_malformed_border() {
+        // two gaps in the top wall
+        assert!(parse("#..###\n#>...#\n####.#\n").is_err());
+        // no gap in the bottom wall
+        assert!(parse("#.####\n#>...#\n######\n").is_err());
+        // gap on the wrong side of the bottom wall
+        assert!(parse("#.####\n#>...#\n#.####\n").is_err());
+        // ragged interior row
+        assert!(parse("#.####\n#>..#\n####.#\n").is_err());
+        // missing the side wall
+        assert!(parse("#.####\n#>....\n####.#\n").is_err());
+        // no rows at all
+        assert!(parse("#.####\n####.#\n").is_err());
+
+        assert!(parse("#.####\n#>...#\n####.#\n").is_ok());
+    }
+
+    #[test]
+    fn test_parse_bad_blizzard() {
+        assert_eq!(
+            parse("#.####\n#>.x.#\n####.#\n").unwrap_err().to_string(),
+            "error parsing valley: error parsing blizzard direction \"x\" in row \"#>.x.#\""
+        );
+    }
+
+    #[test]
+    fn test_occupancy() {
+        let input = advent_of_code::read_example(24).unwrap();
+        let (blizzards, dimensions) = parse(&input).unwrap();
+        let puzzle = Puzzle::new(blizzards, dimensions);
+
+        assert_eq!(puzzle.occupancy.len(), 12);
+
+        // the valley after minute 1, as given in the puzzle
+        let expected: HashSet<(isize, isize)> = [
+            (1, 0),
+            (2, 0),
+            (4, 0),
+            (0, 1),
+            (3, 1),
+            (4, 1),
+            (0, 2),
+            (1, 2),
+            (3, 2),
+            (4, 2),
+            (0, 3),
+            (1, 3),
+            (4, 3),
+            (5, 3),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(puzzle.occupancy[1], expected);
+        assert!(puzzle.is_occupied((4, 3), 13));
+        assert!(!puzzle.is_occupied((2, 3), 13));
+    }
+
+    #[test]
+    fn test_solve_matches_reachability() {
+        let input = advent_of_code::read_example(24).unwrap();
+
+        let (blizzards, dimensions) = parse(&input).unwrap();
+        let mut bfs = Puzzle::new(blizzards.clone(), dimensions);
+        let mut reachability = Puzzle::new(blizzards, dimensions);
+
+        let mut start = ValleyPortal::TopLeft;
+
+        for expected in [18, 23, 13] {
+            assert_eq!(bfs.solve(start), Some(expected));
+            assert_eq!(
+                reachability.solve_by_reachability(start, expected as usize),
+                Some(expected)
+            );
+            assert_eq!(bfs.minute, reachability.minute);
+
+            start = start.other();
+        }
+    }
+
+    #[test]
+    fn test_solve_by_reachability_gives_up() {
+        let input = advent_of_code::read_example(24).unwrap();
+        let (blizzards, dimensions) = parse(&input).unwrap();
+
+        let mut puzzle = Puzzle::new(blizzards, dimensions);
+        assert_eq!(
+            puzzle.solve_by_reachability(ValleyPortal::TopLeft, 17),
+            None
+        );
+
+        // a blizzard that can't leave the only column blocks the way forever
+        assert_eq!(total_time_by_reachability("#.#\n#>#\n#.#\n", 1), None);
+        assert_eq!(total_time("#.#\n#>#\n#.#\n", 1), None);
+    }
 }
@@ -1,6 +1,12 @@
-use std::{fmt::Display, str::FromStr};
+use std::{
+    fmt::Display,
+    iter::Sum,
+    ops::{Add, Mul},
+    str::FromStr,
+};
 
 #[derive(Clone, Copy, Debug)]
+#[allow(clippy::upper_case_acronyms)]
 struct SNAFU(i64);
 
 #[derive(Debug)]
@@ -16,6 +22,55 @@ impl Display for SNAFUParseError {
 
 impl std::error::Error for SNAFUParseError {}
 
+#[derive(Debug)]
+struct SNAFULineError {
+    line: usize,
+    source: SNAFUParseError,
+}
+
+impl Display for SNAFULineError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.source)
+    }
+}
+
+impl std::error::Error for SNAFULineError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+#[derive(Debug)]
+struct SNAFUOverflowError {}
+
+impl Display for SNAFUOverflowError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "value does not fit in a SNAFU number")
+    }
+}
+
+impl std::error::Error for SNAFUOverflowError {}
+
+impl SNAFU {
+    // Builds a SNAFU number from any integer, failing if it won't fit in the i64 backing it.
+    fn checked_from_i64<T: TryInto<i64>>(value: T) -> Result<Self, SNAFUOverflowError> {
+        value
+            .try_into()
+            .map(SNAFU)
+            .map_err(|_| SNAFUOverflowError {})
+    }
+
+    // Widen to i128, where neither the sum nor the product of two i64s can overflow, and only then
+    // check whether the result fits.
+    fn checked_add(self, rhs: Self) -> Result<Self, SNAFUOverflowError> {
+        Self::checked_from_i64(self.0 as i128 + rhs.0 as i128)
+    }
+
+    fn checked_mul(self, rhs: Self) -> Result<Self, SNAFUOverflowError> {
+        Self::checked_from_i64(self.0 as i128 * rhs.0 as i128)
+    }
+}
+
 impl From<SNAFU> for i64 {
     fn from(number: SNAFU) -> Self {
         number.0
@@ -28,79 +83,105 @@ impl From<i64> for SNAFU {
     }
 }
 
+// Overflowing is always a bug, so the operators panic even in release builds where the i64
+// arithmetic would otherwise quietly wrap around. Use checked_add and checked_mul to handle it.
+impl Add for SNAFU {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.checked_add(rhs)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+}
+
+impl Mul for SNAFU {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.checked_mul(rhs)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+}
+
+impl Sum for SNAFU {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(SNAFU(0), |acc, x| acc + x)
+    }
+}
+
 impl FromStr for SNAFU {
     type Err = SNAFUParseError;
 
+    // Read the digits from most to least significant. Once the number so far is out of range no
+    // further digits can bring it back, so stopping there keeps the i128 working from overflowing
+    // however long the input is.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut place_value = 1;
-        let mut cur = 0;
-
-        for ch in s.chars().rev() {
-            cur += place_value
-                * match ch {
-                    '0' => 0,
-                    '1' => 1,
-                    '2' => 2,
-                    '=' => -2,
-                    '-' => -1,
-                    _ => {
-                        return Err(Self::Err {
-                            input: s.to_string(),
-                        })
-                    }
-                };
-
-            place_value *= 5;
+        let err = || Self::Err {
+            input: s.to_string(),
+        };
+        let mut cur = SNAFU(0);
+
+        for ch in s.chars() {
+            let digit = match ch {
+                '0' => 0,
+                '1' => 1,
+                '2' => 2,
+                '=' => -2,
+                '-' => -1,
+                _ => return Err(err()),
+            };
+
+            cur = Self::checked_from_i64(cur.0 as i128 * 5 + digit).map_err(|_| err())?;
         }
 
-        Ok(SNAFU(cur))
+        Ok(cur)
     }
 }
 
-impl Display for SNAFU {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut digits = vec![];
+impl SNAFU {
+    // The balanced base 5 digits of the number, each in -2..=2, from most to least significant.
+    //
+    // Take the digits off the bottom, picking whichever of -2..=2 leaves the remainder divisible by
+    // 5, and carrying into the next place when that's a negative digit. This works just as well for
+    // negative numbers, which simply end up with a negative leading digit. Widen to i128 so the
+    // carry can't overflow at the extremes of i64.
+    fn debug_digits(&self) -> Vec<i8> {
+        if self.0 == 0 {
+            return vec![0];
+        }
 
-        {
-            let mut cur = self.0;
+        let mut digits = vec![];
+        let mut cur = self.0 as i128;
 
-            while cur > 0 {
-                let bit = cur % 5;
-                cur /= 5;
+        while cur != 0 {
+            let digit = match cur.rem_euclid(5) {
+                3 => -2,
+                4 => -1,
+                x => x,
+            };
 
-                digits.push(bit);
-            }
+            cur = (cur - digit) / 5;
+            digits.push(digit as i8);
         }
 
         digits.reverse();
+        digits
+    }
+}
 
-        let digits: Vec<char> = (0..digits.len())
-            .rev()
-            .fold(vec![0], |mut chars, x| {
-                let this = digits[x] + chars.pop().unwrap();
-                let rem = this % 5;
-
-                let carry = if rem == 3 || rem == 4 || this >= 5 {
-                    1
-                } else {
-                    0
-                };
-
-                chars.push(rem);
-                chars.push(carry);
-
-                chars
-            })
+impl Display for SNAFU {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let digits: String = self
+            .debug_digits()
             .into_iter()
-            .map(|x| match x {
-                3 => '=',
-                4 => '-',
+            .map(|digit| match digit {
+                -2 => '=',
+                -1 => '-',
                 x => (x as u8 + b'0') as char,
             })
-            .rev()
             .collect();
 
-        write!(f, "{}", String::from_iter(digits).trim_start_matches("0"))
+        write!(f, "{}", digits)
     }
 }
 
@@ -147,28 +228,192 @@ mod snafu_tests {
             assert_eq!(format!("{}", number), *input, "decimal {}", out);
         }
     }
+
+    #[test]
+    fn test_display_zero() {
+        assert_eq!(format!("{}", SNAFU(0)), "0");
+        assert_eq!(i64::from("0".parse::<SNAFU>().unwrap()), 0);
+    }
+
+    #[test]
+    fn test_display_negative() {
+        for (input, out) in [
+            ("-", -1),
+            ("=", -2),
+            ("-2", -3),
+            ("-1", -4),
+            ("-0", -5),
+            ("=0=", -52),
+        ] {
+            assert_eq!(format!("{}", SNAFU(out)), input, "decimal {}", out);
+            assert_eq!(i64::from(input.parse::<SNAFU>().unwrap()), out);
+        }
+
+        for (_, out) in TESTS.iter() {
+            let negated = format!("{}", SNAFU(-out));
+
+            assert_eq!(i64::from(negated.parse::<SNAFU>().unwrap()), -out);
+        }
+    }
+
+    #[test]
+    fn test_display_extremes() {
+        // rendering the extremes mustn't overflow while carrying
+        assert_eq!(format!("{}", SNAFU(i64::MAX)).len(), 28);
+        assert_eq!(format!("{}", SNAFU(i64::MIN)).len(), 28);
+    }
+
+    #[test]
+    fn test_parse_extremes() {
+        for extreme in [i64::MAX, i64::MIN] {
+            let rendered = format!("{}", SNAFU(extreme));
+
+            assert_eq!(i64::from(rendered.parse::<SNAFU>().unwrap()), extreme);
+        }
+    }
+
+    #[test]
+    fn test_parse_overflow() {
+        // one more place than the extremes, then far longer than fits even in an i128
+        assert!(format!("{}2", SNAFU(i64::MAX)).parse::<SNAFU>().is_err());
+        assert!(format!("{}0", SNAFU(i64::MIN)).parse::<SNAFU>().is_err());
+        assert!("2".repeat(100).parse::<SNAFU>().is_err());
+
+        // leading zeros don't count towards the size
+        assert_eq!(
+            i64::from(format!("{}1", "0".repeat(100)).parse::<SNAFU>().unwrap()),
+            1
+        );
+    }
+
+    #[test]
+    fn test_debug_digits() {
+        assert_eq!(SNAFU(0).debug_digits(), vec![0]);
+        assert_eq!(SNAFU(3).debug_digits(), vec![1, -2]);
+        assert_eq!(SNAFU(2022).debug_digits(), vec![1, -2, 1, 1, -1, 2]);
+        assert_eq!(SNAFU(12345).debug_digits(), vec![1, -1, 0, -1, -1, -1, 0]);
+        assert_eq!(SNAFU(-3).debug_digits(), vec![-1, 2]);
+
+        for (_, out) in TESTS.iter() {
+            let digits = SNAFU(*out).debug_digits();
+
+            assert!(digits.iter().all(|digit| (-2..=2).contains(digit)));
+            assert_eq!(digits.iter().fold(0, |acc, &d| acc * 5 + d as i64), *out);
+        }
+    }
+
+    #[test]
+    fn test_add() {
+        let a: SNAFU = "1=".parse().unwrap();
+        let b: SNAFU = "2-".parse().unwrap();
+
+        assert_eq!(format!("{}", a + b), "22");
+    }
+
+    #[test]
+    fn test_mul() {
+        for (a, x) in TESTS.iter().take(10) {
+            for (b, y) in TESTS.iter().take(10) {
+                let product = a.parse::<SNAFU>().unwrap() * b.parse::<SNAFU>().unwrap();
+
+                assert_eq!(format!("{}", product), format!("{}", SNAFU(x * y)));
+                assert_eq!(i64::from(product), x * y);
+            }
+        }
+
+        let a: SNAFU = "1=11-2".parse().unwrap();
+        let b: SNAFU = "1-0---0".parse().unwrap();
+
+        assert_eq!(i64::from(a * b), 2022 * 12345);
+    }
+
+    #[test]
+    fn test_checked_from_i64() {
+        assert_eq!(i64::from(SNAFU::checked_from_i64(2022).unwrap()), 2022);
+        assert_eq!(
+            i64::from(SNAFU::checked_from_i64(i64::MAX as u64).unwrap()),
+            i64::MAX
+        );
+
+        assert!(SNAFU::checked_from_i64(i64::MAX as u64 + 1).is_err());
+        assert!(SNAFU::checked_from_i64(i64::MIN as i128 - 1).is_err());
+        assert!(SNAFU::checked_from_i64(2022_i128 * i64::MAX as i128).is_err());
+    }
+
+    #[test]
+    fn test_checked_arithmetic() {
+        assert_eq!(
+            i64::from(SNAFU(2022).checked_add(SNAFU(12345)).unwrap()),
+            14367
+        );
+        assert_eq!(i64::from(SNAFU(-3).checked_mul(SNAFU(5)).unwrap()), -15);
+        assert_eq!(
+            i64::from(SNAFU(i64::MIN).checked_add(SNAFU(i64::MAX)).unwrap()),
+            -1
+        );
+
+        assert!(SNAFU(i64::MAX).checked_add(SNAFU(1)).is_err());
+        assert!(SNAFU(i64::MIN).checked_add(SNAFU(-1)).is_err());
+        assert!(SNAFU(i64::MAX).checked_mul(SNAFU(2)).is_err());
+        assert!(SNAFU(i64::MIN).checked_mul(SNAFU(-1)).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "value does not fit in a SNAFU number")]
+    fn test_mul_overflow() {
+        let _ = SNAFU(i64::MAX) * SNAFU(5);
+    }
+
+    #[test]
+    #[should_panic(expected = "value does not fit in a SNAFU number")]
+    fn test_sum_overflow() {
+        let _: SNAFU = [SNAFU(i64::MAX), SNAFU(1)].into_iter().sum();
+    }
+
+    #[test]
+    fn test_sum() {
+        let numbers: Vec<SNAFU> = TESTS
+            .iter()
+            .map(|(input, _)| input.parse().unwrap())
+            .collect();
+
+        let sum: SNAFU = numbers.iter().copied().sum();
+        let decimal: i64 = numbers.iter().map(|&x| i64::from(x)).sum();
+
+        assert_eq!(i64::from(sum), decimal);
+        assert_eq!(format!("{}", sum), format!("{}", SNAFU::from(decimal)));
+    }
 }
 
-pub fn part_one(input: &str) -> Option<String> {
-    let numbers: Vec<i64> = input
+// Sums the SNAFU number on each line of the input, reporting the (1-based) line of the first number
+// which can't be parsed.
+fn total(input: &str) -> Result<SNAFU, SNAFULineError> {
+    input
         .lines()
-        .map(|snafu| snafu.parse::<SNAFU>())
-        .collect::<Result<Vec<SNAFU>, SNAFUParseError>>()
-        .expect("parse error")
-        .iter()
-        .map(|&snafu| i64::from(snafu))
-        .collect();
+        .enumerate()
+        .map(|(i, snafu)| {
+            snafu.parse::<SNAFU>().map_err(|source| SNAFULineError {
+                line: i + 1,
+                source,
+            })
+        })
+        .sum()
+}
+
+pub fn part_one(input: &str) -> Option<String> {
+    let sum = total(input).unwrap_or_else(|err| panic!("{}", err));
 
-    let sum: i64 = numbers.iter().sum();
-    Some(format!("{}", SNAFU::from(sum)))
+    Some(format!("{}", sum))
 }
 
-pub fn part_two(input: &str) -> Option<i64> {
+// There is no part two on the last day: the final star is awarded for having collected all of the
+// others, so this is intentionally left unsolved.
+pub fn part_two(_input: &str) -> Option<i64> {
     None
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 25);
+    let input = &advent_of_code::read_input_or_stdin(25);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
@@ -179,13 +424,31 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 25);
+        let input = advent_of_code::read_example(25).unwrap();
         assert_eq!(part_one(&input), Some("2=-1=0".to_string()));
     }
 
+    #[test]
+    fn test_invalid_digit() {
+        let err = total("1=\n2-\n1=3-\n12\n").unwrap_err();
+
+        assert_eq!(err.line, 3);
+        assert_eq!(err.source.input, "1=3-");
+        assert_eq!(
+            format!("{}", err),
+            "line 3: error while parsing SNAFU number 1=3-"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "line 2: error while parsing SNAFU number 3")]
+    fn test_part_one_invalid_digit() {
+        part_one("1=\n3\n");
+    }
+
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 25);
+        let input = advent_of_code::read_example(25).unwrap();
         assert_eq!(part_two(&input), None);
     }
 }
@@ -0,0 +1,48 @@
+/*
+ * Runs a single part of any day without needing to know which binary it lives in.
+ * Example: `cargo run --bin aoc -- --day 16 --part 2`
+ */
+use std::process;
+
+struct Args {
+    day: u8,
+    part: u8,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    Ok(Args {
+        day: args.value_from_str(["-d", "--day"])?,
+        part: args.value_from_str(["-p", "--part"])?,
+    })
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let solver = match advent_of_code::solutions::part(args.day, args.part) {
+        Some(solver) => solver,
+        None => {
+            eprintln!(
+                "There is no solution for day {} part {}.",
+                args.day, args.part
+            );
+            process::exit(1);
+        }
+    };
+
+    let input = advent_of_code::read_input_or_stdin(args.day);
+
+    if let Err(e) = &input {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+
+    advent_of_code::solve!(args.part, solver, &input);
+}
@@ -3,7 +3,7 @@
  * There is no need to edit this file unless you want to change template functionality.
  */
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
     process,
 };
@@ -17,7 +17,7 @@ pub fn part_two(input: &str) -> Option<u32> {
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", DAY);
+    let input = &advent_of_code::read_input_or_stdin(DAY);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
@@ -28,13 +28,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", DAY);
+        let input = advent_of_code::read_example(DAY).unwrap();
         assert_eq!(part_one(&input), None);
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", DAY);
+        let input = advent_of_code::read_example(DAY).unwrap();
         assert_eq!(part_two(&input), None);
     }
 }
@@ -49,8 +49,55 @@ fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
     OpenOptions::new().write(true).create_new(true).open(path)
 }
 
+// Leaves an existing file as it is, so scaffolding a day again doesn't lose its input.
 fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+}
+
+// Adds the day to the days! list in src/solutions.rs, keeping the list in order, so that the library
+// and its tooling know about it. Returns whether the day needed adding.
+fn register_day(day: u8) -> Result<bool, std::io::Error> {
+    let path = "src/solutions.rs";
+    let contents = fs::read_to_string(path)?;
+    let entry = format!(
+        "    {} => d{:02} Day{:02} \"bin/{:02}.rs\",",
+        day, day, day, day
+    );
+
+    if contents.lines().any(|line| line == entry) {
+        return Ok(false);
+    }
+
+    let mut lines: Vec<&str> = contents.lines().collect();
+    let start = lines
+        .iter()
+        .position(|&line| line == "days! {")
+        .expect("no days! list in src/solutions.rs")
+        + 1;
+    let end = start
+        + lines[start..]
+            .iter()
+            .position(|&line| line == "}")
+            .expect("days! list in src/solutions.rs is never closed");
+
+    let at = (start..end)
+        .find(|&i| {
+            let listed: Option<u8> = lines[i]
+                .split_whitespace()
+                .next()
+                .and_then(|n| n.parse().ok());
+            listed.is_some_and(|listed| listed > day)
+        })
+        .unwrap_or(end);
+
+    lines.insert(at, &entry);
+    fs::write(path, lines.join("\n") + "\n")?;
+
+    Ok(true)
 }
 
 fn main() {
@@ -86,6 +133,17 @@ fn main() {
         }
     }
 
+    match register_day(day) {
+        Ok(true) => {
+            println!("Registered day {} in \"src/solutions.rs\"", day);
+        }
+        Ok(false) => {}
+        Err(e) => {
+            eprintln!("Failed to register day in src/solutions.rs: {}", e);
+            process::exit(1);
+        }
+    }
+
     match create_file(&input_path) {
         Ok(_) => {
             println!("Created empty input file \"{}\"", &input_path);
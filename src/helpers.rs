@@ -3,35 +3,380 @@
  * Example import from this file: `use advent_of_code::helpers::example_fn;`.
  */
 
-use std::{fmt::Display, slice::Iter};
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    convert::Infallible,
+    env,
+    fmt::Display,
+    hash::Hash,
+    io::{self, IsTerminal},
+    ops::{Add, Index, IndexMut, Sub},
+    slice::{Iter, IterMut},
+    str::FromStr,
+};
 
-#[derive(Clone, Copy, Debug)]
+// Whether solutions should print their working, which is handy for debugging but far too noisy to
+// leave on by default. Set the AOC_VERBOSE environment variable to turn it on.
+pub fn verbose() -> bool {
+    env::var_os("AOC_VERBOSE").is_some()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+}
+
+impl Color {
+    // The ANSI escape code to set this as the foreground colour.
+    fn code(self) -> u8 {
+        match self {
+            Self::Red => 31,
+            Self::Green => 32,
+            Self::Yellow => 33,
+            Self::Blue => 34,
+            Self::Magenta => 35,
+            Self::Cyan => 36,
+        }
+    }
+}
+
+// Colours in the characters of a rendered grid according to the legend, so that the interesting
+// bits stand out. This only happens if the AOC_COLOR environment variable is set to 1 and we're
+// printing to a terminal; otherwise the grid comes back as it was, so it's safe to use anywhere.
+pub fn render_colored(grid_string: &str, legend: &[(char, Color)]) -> String {
+    let enabled = env::var_os("AOC_COLOR").is_some_and(|value| value == "1");
+    paint(grid_string, legend, enabled && io::stdout().is_terminal())
+}
+
+fn paint(grid_string: &str, legend: &[(char, Color)], enabled: bool) -> String {
+    if !enabled {
+        return grid_string.to_string();
+    }
+
+    grid_string
+        .chars()
+        .map(
+            |ch| match legend.iter().find(|(character, _)| *character == ch) {
+                Some((_, color)) => format!("\x1b[{}m{}\x1b[0m", color.code(), ch),
+                None => ch.to_string(),
+            },
+        )
+        .collect()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct Point {
     pub x: usize,
     pub y: usize,
 }
 
+impl Point {
+    pub fn manhattan(&self, other: &Point) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    // Subtraction which gives None rather than panicking when either coordinate would go negative,
+    // e.g. when looking left of the grid's first column.
+    pub fn checked_sub(&self, other: &Point) -> Option<Point> {
+        Some(Point {
+            x: self.x.checked_sub(other.x)?,
+            y: self.y.checked_sub(other.y)?,
+        })
+    }
+
+    // Moves the point by the given offsets, or gives None if it would leave the range of usize.
+    pub fn translate(&self, dx: isize, dy: isize) -> Option<Point> {
+        Some(Point {
+            x: self.x.checked_add_signed(dx)?,
+            y: self.y.checked_add_signed(dy)?,
+        })
+    }
+}
+
+impl Add for Point {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Point {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+        }
+    }
+}
+
+impl Sub for Point {
+    type Output = Self;
+
+    // Going negative is always a bug, so panic even in release builds where the usize arithmetic
+    // would otherwise quietly wrap around.
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.checked_sub(&rhs)
+            .expect("point subtraction underflowed")
+    }
+}
+
+#[derive(Debug)]
+pub struct PointParseError {
+    input: String,
+    component: &'static str,
+}
+
+impl Display for PointParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "error parsing point {:?}: missing or invalid {}",
+            self.input, self.component
+        )
+    }
+}
+
+impl std::error::Error for PointParseError {}
+
+// A part of the puzzle input that couldn't be parsed, along with what it was meant to be, for the
+// days with nothing more useful to say about why.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub context: &'static str,
+    pub input: String,
+}
+
+impl ParseError {
+    pub fn new(context: &'static str, input: impl ToString) -> ParseError {
+        ParseError {
+            context,
+            input: input.to_string(),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "error parsing {} {:?}", self.context, self.input)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// Splits a point written as "x,y", as in most of the puzzle inputs, into its two coordinates.
+fn parse_coordinates<T: FromStr>(s: &str) -> Result<(T, T), PointParseError> {
+    let error = |component| PointParseError {
+        input: s.to_string(),
+        component,
+    };
+
+    let (x, y) = s.split_once(',').ok_or_else(|| error("comma"))?;
+
+    Ok((
+        x.trim().parse().map_err(|_| error("x coordinate"))?,
+        y.trim().parse().map_err(|_| error("y coordinate"))?,
+    ))
+}
+
+impl FromStr for Point {
+    type Err = PointParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (x, y) = parse_coordinates(s)?;
+
+        Ok(Point { x, y })
+    }
+}
+
 impl From<&str> for Point {
     fn from(s: &str) -> Self {
-        let (x, y) = s.split_once(',').unwrap();
-        Point {
-            x: x.parse().unwrap(),
-            y: y.parse().unwrap(),
+        s.parse().unwrap()
+    }
+}
+
+// A point which is allowed to wander off into negative coordinates, for the puzzles which aren't
+// confined to a grid.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct IPoint {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl IPoint {
+    pub fn manhattan(&self, other: &IPoint) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    // The points above, right, below and left of this one, in that order, as for
+    // Grid::orthogonal_neighbours.
+    pub fn neighbours4(&self) -> [IPoint; 4] {
+        [(0, -1), (1, 0), (0, 1), (-1, 0)].map(|(x, y)| *self + IPoint { x, y })
+    }
+
+    // As neighbours4, but including the diagonals too, clockwise from the top left as for
+    // Grid::all_neighbours.
+    pub fn neighbours8(&self) -> [IPoint; 8] {
+        [
+            (-1, -1),
+            (0, -1),
+            (1, -1),
+            (1, 0),
+            (1, 1),
+            (0, 1),
+            (-1, 1),
+            (-1, 0),
+        ]
+        .map(|(x, y)| *self + IPoint { x, y })
+    }
+}
+
+impl Add for IPoint {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        IPoint {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+        }
+    }
+}
+
+impl Sub for IPoint {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        IPoint {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+        }
+    }
+}
+
+impl FromStr for IPoint {
+    type Err = PointParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (x, y) = parse_coordinates(s)?;
+
+        Ok(IPoint { x, y })
+    }
+}
+
+// The four compass directions, numbered clockwise from north so that turning is just arithmetic
+// on the discriminant. North is towards smaller y, as in the puzzle inputs where the first line is
+// the top row.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North = 0,
+    East = 1,
+    South = 2,
+    West = 3,
+}
+
+impl Direction {
+    pub fn from_index(i: usize) -> Direction {
+        match i % 4 {
+            0 => Self::North,
+            1 => Self::East,
+            2 => Self::South,
+            _ => Self::West,
+        }
+    }
+
+    pub fn turn_clockwise(self) -> Direction {
+        Self::from_index(self as usize + 1)
+    }
+
+    pub fn turn_anticlockwise(self) -> Direction {
+        Self::from_index(self as usize + 3)
+    }
+
+    pub fn opposite(self) -> Direction {
+        Self::from_index(self as usize + 2)
+    }
+
+    // The change in (x, y) from taking one step in this direction.
+    pub fn delta(self) -> (isize, isize) {
+        match self {
+            Self::North => (0, -1),
+            Self::East => (1, 0),
+            Self::South => (0, 1),
+            Self::West => (-1, 0),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct DirectionParseError {}
+
+impl Display for DirectionParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "error parsing direction")
+    }
+}
+
+impl std::error::Error for DirectionParseError {}
+
+impl TryFrom<char> for Direction {
+    type Error = DirectionParseError;
+
+    // The puzzles write directions as compass points, as up/down/left/right or as arrows, so accept
+    // any of them.
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            'N' | 'U' | '^' => Ok(Self::North),
+            'E' | 'R' | '>' => Ok(Self::East),
+            'S' | 'D' | 'v' => Ok(Self::South),
+            'W' | 'L' | '<' => Ok(Self::West),
+            _ => Err(Self::Error {}),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum GridError {
+    // A row wasn't as long as the first one.
+    RaggedRow {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl Display for GridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::RaggedRow {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "row {} of the grid has {} values, but the rows before it have {}",
+                row, found, expected
+            ),
         }
     }
 }
 
+impl std::error::Error for GridError {}
+
 // I really just need to build a library that gives me a 1D grid that models an arbitrary sized
 // rectangle, with lookup from (x,y) coordinates into the grid values. The number of times I
 // implement this gives me lots and lots of practice. Perhaps today is the day?
 //
 // Oh look, I did it. Well, I pulled it out of the day's problem anyway
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Grid<T>
 where
     T: Clone + Default,
 {
     values: Vec<T>,
     width: usize,
+    // Kept alongside the width rather than worked out from it, so that grids with no columns still
+    // know how many rows they have.
+    height: usize,
 }
 
 impl<T> Grid<T>
@@ -40,33 +385,342 @@ where
 {
     pub fn new(width: usize, height: usize) -> Grid<T> {
         Grid {
-            values: vec![Default::default(); width * (height + 1)],
+            values: vec![Default::default(); width * height],
+            width,
+            height,
+        }
+    }
+
+    // Builds a grid from its rows, which must all be the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Grid<T>, GridError> {
+        let width = rows.first().map_or(0, Vec::len);
+        let height = rows.len();
+
+        if let Some((row, found)) = rows
+            .iter()
+            .map(Vec::len)
+            .enumerate()
+            .find(|&(_, len)| len != width)
+        {
+            return Err(GridError::RaggedRow {
+                row,
+                expected: width,
+                found,
+            });
+        }
+
+        Ok(Grid {
+            values: rows.into_iter().flatten().collect(),
             width,
+            height,
+        })
+    }
+
+    // Builds a grid with a row per line of the input, mapping each character to a value with f.
+    // Lines needn't all be the same length: the grid is as wide as the longest line, and the end of
+    // any shorter line is padded out with default values.
+    pub fn from_lines<F: Fn(char) -> T>(input: &str, f: F) -> Grid<T> {
+        match Self::from_str_with(input, |ch| Ok::<T, Infallible>(f(ch))) {
+            Ok(grid) => grid,
+            Err(never) => match never {},
+        }
+    }
+
+    // As from_lines, for when not every character makes a valid value. The first error from f is
+    // returned.
+    pub fn from_str_with<F, E>(input: &str, f: F) -> Result<Grid<T>, E>
+    where
+        F: Fn(char) -> Result<T, E>,
+    {
+        let lines: Vec<&str> = input.lines().collect();
+        let width = lines
+            .iter()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        let mut grid = Grid::new(width, lines.len());
+
+        for (y, line) in lines.iter().enumerate() {
+            for (x, ch) in line.chars().enumerate() {
+                *grid.point_mut(&Point { x, y }) = f(ch)?;
+            }
+        }
+
+        Ok(grid)
+    }
+
+    // Adds extra columns of default values to either side of the grid and extra rows to the bottom.
+    // Everything already in the grid keeps its place relative to everything else, but moves right
+    // by the number of columns added on the left, which is returned so callers can adjust their
+    // coordinates to match.
+    pub fn expand(&mut self, left: usize, right: usize, bottom: usize) -> usize {
+        let width = left + self.width + right;
+        let height = self.height + bottom;
+        let mut values = Vec::with_capacity(width * height);
+
+        for y in 0..self.height {
+            values.resize(values.len() + left, Default::default());
+            values.extend_from_slice(self.row(y));
+            values.resize(values.len() + right, Default::default());
         }
+
+        values.resize(width * height, Default::default());
+
+        self.values = values;
+        self.width = width;
+        self.height = height;
+
+        left
+    }
+
+    // Adds extra columns of default values to the right of the grid. Everything already in the grid
+    // stays at the same point.
+    pub fn grow_width(&mut self, extra: usize) {
+        self.expand(0, extra, 0);
+    }
+
+    // Adds extra rows of default values to the bottom of the grid.
+    pub fn grow_height(&mut self, extra: usize) {
+        self.expand(0, 0, extra);
     }
 
     pub fn point(&self, point: &Point) -> &T {
-        &self.values[self.width * point.y + point.x]
+        &self.values[point_to_index(self.width, point)]
     }
 
     pub fn point_mut(&mut self, point: &Point) -> &mut T {
-        &mut self.values[self.width * point.y + point.x]
+        &mut self.values[point_to_index(self.width, point)]
+    }
+
+    pub fn get(&self, point: &Point) -> Option<&T> {
+        if self.is_out_of_bounds(point) {
+            None
+        } else {
+            Some(self.point(point))
+        }
+    }
+
+    pub fn get_mut(&mut self, point: &Point) -> Option<&mut T> {
+        if self.is_out_of_bounds(point) {
+            None
+        } else {
+            Some(self.point_mut(point))
+        }
+    }
+
+    pub fn get_signed(&self, x: isize, y: isize) -> Option<&T> {
+        if x < 0 || y < 0 {
+            None
+        } else {
+            self.get(&Point {
+                x: x as usize,
+                y: y as usize,
+            })
+        }
     }
 
+    // Looks up a point as if the grid were a torus, so walking off one edge brings you back in on
+    // the opposite edge.
+    pub fn wrapping_point(&self, x: isize, y: isize) -> &T {
+        self.point(&Point {
+            x: x.rem_euclid(self.width as isize) as usize,
+            y: y.rem_euclid(self.height() as isize) as usize,
+        })
+    }
+
+    // Both coordinates need checking: an x past the end of a row would otherwise land on the next
+    // row down and look perfectly valid.
     pub fn is_out_of_bounds(&self, point: &Point) -> bool {
-        self.width * point.y + point.x >= self.values.len()
+        point.x >= self.width || point.y >= self.height()
+    }
+
+    // The points above, right, below and left of the given point, in that order, skipping any which
+    // fall outside the grid.
+    pub fn orthogonal_neighbours(&self, point: &Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbours(point, &[(0, -1), (1, 0), (0, 1), (-1, 0)])
+    }
+
+    // orthogonal_neighbours and all_neighbours collected, for building adjacency lists.
+    pub fn neighbours4(&self, p: Point) -> Vec<Point> {
+        self.orthogonal_neighbours(&p).collect()
+    }
+
+    pub fn neighbours8(&self, p: Point) -> Vec<Point> {
+        self.all_neighbours(&p).collect()
+    }
+
+    // As orthogonal_neighbours, but including the diagonals too, clockwise from the top left.
+    pub fn all_neighbours(&self, point: &Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbours(
+            point,
+            &[
+                (-1, -1),
+                (0, -1),
+                (1, -1),
+                (1, 0),
+                (1, 1),
+                (0, 1),
+                (-1, 1),
+                (-1, 0),
+            ],
+        )
+    }
+
+    fn neighbours(
+        &self,
+        point: &Point,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = Point> + '_ {
+        let point = *point;
+
+        offsets.iter().filter_map(move |&(dx, dy)| {
+            point
+                .translate(dx, dy)
+                .filter(|neighbour| !self.is_out_of_bounds(neighbour))
+        })
+    }
+
+    // Every point that can be reached from the start by moving up, down, left or right through
+    // passable values, including the start itself. The fill stops at the edges of the grid rather
+    // than wrapping around. If the start isn't passable, nothing can be reached.
+    pub fn flood_fill<F: Fn(&T) -> bool>(&self, start: Point, passable: F) -> HashSet<Point> {
+        let mut reached = HashSet::new();
+
+        if !self.get(&start).is_some_and(&passable) {
+            return reached;
+        }
+
+        let mut queue = VecDeque::from([start]);
+        reached.insert(start);
+
+        while let Some(point) = queue.pop_front() {
+            for neighbour in self.orthogonal_neighbours(&point) {
+                if passable(&self[neighbour]) && reached.insert(neighbour) {
+                    queue.push_back(neighbour);
+                }
+            }
+        }
+
+        reached
+    }
+
+    // The points of every value matching the predicate, in row-major order.
+    pub fn positions_of<'a, P: Fn(&T) -> bool + 'a>(
+        &'a self,
+        pred: P,
+    ) -> impl Iterator<Item = Point> + 'a {
+        self.enumerate()
+            .filter(move |(_, value)| pred(value))
+            .map(|(point, _)| point)
+    }
+
+    pub fn count<P: Fn(&T) -> bool>(&self, pred: P) -> usize {
+        self.values.iter().filter(|&value| pred(value)).count()
+    }
+
+    // Builds a grid of the same shape by transforming every value.
+    pub fn map<U: Clone + Default, F: Fn(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid {
+            values: self.values.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.values[y * self.width..(y + 1) * self.width]
+    }
+
+    // The values in a column, from top to bottom.
+    pub fn col(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> + '_ {
+        (0..self.height).map(move |y| self.point(&Point { x, y }))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    // Flips the grid over its diagonal, so the rows become columns and vice versa.
+    pub fn transpose(&self) -> Grid<T> {
+        Grid {
+            values: (0..self.width).flat_map(|x| self.col(x).cloned()).collect(),
+            width: self.height,
+            height: self.width,
+        }
     }
 
-    pub fn iter(&self) -> Iter<T> {
+    pub fn iter(&self) -> Iter<'_, T> {
         self.values.iter()
     }
 
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        self.values.iter_mut()
+    }
+
+    // Iterates over the values in row-major order alongside the point each one is at.
+    pub fn enumerate(&self) -> impl Iterator<Item = (Point, &T)> + '_ {
+        let width = self.width;
+
+        self.values
+            .iter()
+            .enumerate()
+            .map(move |(i, value)| (index_to_point(width, i), value))
+    }
+
+    // As enumerate, but the values can be changed in place.
+    pub fn enumerate_mut(&mut self) -> impl Iterator<Item = (Point, &mut T)> + '_ {
+        let width = self.width;
+
+        self.values
+            .iter_mut()
+            .enumerate()
+            .map(move |(i, value)| (index_to_point(width, i), value))
+    }
+
     pub fn width(&self) -> usize {
         self.width
     }
 
     pub fn height(&self) -> usize {
-        self.values.len() / self.width
+        self.height
+    }
+}
+
+impl<T> Index<Point> for Grid<T>
+where
+    T: Clone + Default,
+{
+    type Output = T;
+
+    fn index(&self, point: Point) -> &Self::Output {
+        self.point(&point)
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T>
+where
+    T: Clone + Default,
+{
+    fn index_mut(&mut self, point: Point) -> &mut Self::Output {
+        self.point_mut(&point)
+    }
+}
+
+impl<T> Grid<T>
+where
+    T: Clone + Default + Display,
+{
+    // Draws the grid with a line per row, each value drawn using its Display implementation.
+    pub fn render(&self) -> String {
+        (0..self.height())
+            .map(|y| {
+                (0..self.width())
+                    .map(|x| self.point(&Point { x, y }).to_string())
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
     }
 }
 
@@ -75,16 +729,1212 @@ where
     T: Clone + Default + Display,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut s = String::new();
+        writeln!(f, "{}", self.render())
+    }
+}
 
-        for row in 0..self.height() {
-            for col in 0..self.width() {
-                s.push_str(format!("{}", self.point(&Point { x: col, y: row })).as_str());
-            }
+// A grid which only stores the points that have something in them, for when most of it would be
+// empty or it's not known up front how big it needs to be. It keeps track of the smallest
+// rectangle containing everything that's been set.
+#[derive(Clone, Debug, Default)]
+pub struct SparseGrid<T> {
+    values: HashMap<Point, T>,
+    bounds: Option<(Point, Point)>,
+}
 
-            s.push('\n');
+impl<T> SparseGrid<T> {
+    pub fn new() -> SparseGrid<T> {
+        SparseGrid {
+            values: HashMap::new(),
+            bounds: None,
         }
+    }
+
+    pub fn get(&self, point: &Point) -> Option<&T> {
+        self.values.get(point)
+    }
+
+    // Puts a value at the point, returning whatever was there before.
+    pub fn set(&mut self, point: Point, value: T) -> Option<T> {
+        self.bounds = Some(match self.bounds {
+            None => (point, point),
+            Some((min, max)) => (
+                Point {
+                    x: min.x.min(point.x),
+                    y: min.y.min(point.y),
+                },
+                Point {
+                    x: max.x.max(point.x),
+                    y: max.y.max(point.y),
+                },
+            ),
+        });
+
+        self.values.insert(point, value)
+    }
+
+    pub fn contains(&self, point: &Point) -> bool {
+        self.values.contains_key(point)
+    }
+
+    // The top-left and bottom-right corners of everything set so far, inclusive, or None if the grid
+    // is empty.
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        self.bounds
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Point, &T)> + '_ {
+        self.values.iter()
+    }
+}
+
+impl<T> SparseGrid<T>
+where
+    T: Display,
+{
+    // Draws the part of the grid within its bounds, with empty points drawn as the given character.
+    pub fn render(&self, empty: char) -> String {
+        let Some((min, max)) = self.bounds else {
+            return String::new();
+        };
+
+        (min.y..=max.y)
+            .map(|y| {
+                (min.x..=max.x)
+                    .map(|x| match self.get(&Point { x, y }) {
+                        Some(value) => value.to_string(),
+                        None => empty.to_string(),
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+// The sum of the n largest values, in any order, or of all of them if there are fewer than n. Only
+// the best n seen so far are kept, in a min-heap, so nothing needs sorting.
+pub fn top_n(values: &[u32], n: usize) -> u32 {
+    let mut best = BinaryHeap::with_capacity(n + 1);
+
+    for &value in values {
+        best.push(Reverse(value));
+
+        if best.len() > n {
+            best.pop();
+        }
+    }
+
+    best.into_iter().map(|Reverse(value)| value).sum()
+}
+
+// Converts between a point and its position in a grid stored flat in row-major order, for puzzles
+// that are easier to solve with each cell numbered.
+pub fn index_to_point(width: usize, i: usize) -> Point {
+    Point {
+        x: i % width,
+        y: i / width,
+    }
+}
+
+pub fn point_to_index(width: usize, point: &Point) -> usize {
+    width * point.y + point.x
+}
+
+// The puzzle input as a grid of its characters, along with the length of each row so that cells can
+// be numbered with point_to_index.
+pub fn parse_char_grid(input: &str) -> (Grid<char>, usize) {
+    let grid = Grid::from_lines(input, |ch| ch);
+    let width = grid.width();
+
+    (grid, width)
+}
+
+// Breadth first search from start to the nearest node satisfying is_goal, where every step costs the
+// same. Returns the number of steps taken and the path, start and goal included. Each node is only
+// explored once, so a search over a finite set of nodes always terminates.
+pub fn bfs<N, F, I, G>(start: N, mut neighbours: F, is_goal: G) -> Option<(usize, Vec<N>)>
+where
+    N: Clone + Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+    G: Fn(&N) -> bool,
+{
+    // The node each node was first reached from, which is also how we know we've seen it.
+    let mut parents: HashMap<N, Option<N>> = HashMap::from([(start.clone(), None)]);
+    let mut fringe = VecDeque::from([(start, 0)]);
+
+    while let Some((node, steps)) = fringe.pop_front() {
+        if is_goal(&node) {
+            let mut path = vec![node];
+
+            while let Some(Some(parent)) = parents.get(path.last().unwrap()) {
+                path.push(parent.clone());
+            }
+
+            path.reverse();
+            return Some((steps, path));
+        }
+
+        for next in neighbours(&node) {
+            if !parents.contains_key(&next) {
+                parents.insert(next.clone(), Some(node.clone()));
+                fringe.push_back((next, steps + 1));
+            }
+        }
+    }
+
+    None
+}
+
+// Breadth first expansion from every start at once, giving the number of steps from the nearest
+// start to each node that can be reached at all. The starts themselves are zero steps away.
+pub fn bfs_distances<N, F, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: F,
+) -> HashMap<N, u32>
+where
+    N: Clone + Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    let mut distances = HashMap::new();
+    let mut fringe = VecDeque::new();
+
+    for start in starts {
+        if !distances.contains_key(&start) {
+            distances.insert(start.clone(), 0);
+            fringe.push_back(start);
+        }
+    }
+
+    while let Some(node) = fringe.pop_front() {
+        let steps = distances[&node] + 1;
+
+        for next in neighbours(&node) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), steps);
+                fringe.push_back(next);
+            }
+        }
+    }
+
+    distances
+}
+
+// Dijkstra's algorithm from the start to the nearest node satisfying is_goal, returning the cost of
+// getting there and the path taken. It's A* without a heuristic to guide it, which explores more
+// but can't be led astray.
+pub fn dijkstra<N, F, I, G>(start: N, neighbours: F, is_goal: G) -> Option<(u32, Vec<N>)>
+where
+    N: Clone + Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, u32)>,
+    G: Fn(&N) -> bool,
+{
+    astar([start], neighbours, |_| 0, is_goal)
+}
+
+// A* search from any of the starting nodes to the nearest node satisfying is_goal, returning the
+// cost of getting there and the path taken, start and goal included. neighbours gives the nodes
+// reachable in one step from a node along with the cost of that step. The heuristic must never
+// overestimate the remaining cost, or the path found may not be the cheapest; one which always
+// returns 0 makes this Dijkstra's algorithm.
+//
+// Nodes are numbered as they're discovered so the heap only has to order numbers, not nodes.
+pub fn astar<N, S, F, I, H, G>(
+    starts: S,
+    mut neighbours: F,
+    heuristic: H,
+    is_goal: G,
+) -> Option<(u32, Vec<N>)>
+where
+    N: Clone + Eq + Hash,
+    S: IntoIterator<Item = N>,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, u32)>,
+    H: Fn(&N) -> u32,
+    G: Fn(&N) -> bool,
+{
+    let mut nodes: Vec<N> = vec![];
+    let mut ids: HashMap<N, usize> = HashMap::new();
+    let mut costs: Vec<u32> = vec![];
+    let mut parents: Vec<Option<usize>> = vec![];
+
+    let mut id_of = |node: N, nodes: &mut Vec<N>, costs: &mut Vec<u32>, parents: &mut Vec<_>| {
+        *ids.entry(node.clone()).or_insert_with(|| {
+            nodes.push(node);
+            costs.push(u32::MAX);
+            parents.push(None);
+            nodes.len() - 1
+        })
+    };
+
+    let mut fringe = BinaryHeap::new();
+
+    for start in starts {
+        let id = id_of(start, &mut nodes, &mut costs, &mut parents);
+        costs[id] = 0;
+        fringe.push(Reverse((heuristic(&nodes[id]), 0, id)));
+    }
+
+    while let Some(Reverse((_, cost, id))) = fringe.pop() {
+        if cost > costs[id] {
+            // we've since found a cheaper way here, which has already been explored
+            continue;
+        }
+
+        if is_goal(&nodes[id]) {
+            let mut path = vec![nodes[id].clone()];
+            let mut cur = id;
+
+            while let Some(parent) = parents[cur] {
+                path.push(nodes[parent].clone());
+                cur = parent;
+            }
+
+            path.reverse();
+            return Some((cost, path));
+        }
+
+        let node = nodes[id].clone();
+
+        for (next, step) in neighbours(&node) {
+            let next = id_of(next, &mut nodes, &mut costs, &mut parents);
+            let next_cost = cost + step;
+
+            if next_cost < costs[next] {
+                costs[next] = next_cost;
+                parents[next] = Some(id);
+                fringe.push(Reverse((
+                    next_cost + heuristic(&nodes[next]),
+                    next_cost,
+                    next,
+                )));
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paint() {
+        let grid = "#.o\n.oo";
+        let legend = [('o', Color::Yellow), ('#', Color::Blue)];
+
+        assert_eq!(
+            paint(grid, &legend, true),
+            "\x1b[34m#\x1b[0m.\x1b[33mo\x1b[0m\n.\x1b[33mo\x1b[0m\x1b[33mo\x1b[0m"
+        );
+        assert_eq!(paint(grid, &legend, false), grid);
+    }
+
+    #[test]
+    fn test_grid_dimensions() {
+        let grid: Grid<u8> = Grid::new(3, 4);
+
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 4);
+        assert_eq!(grid.iter().count(), 12);
+    }
+
+    #[test]
+    fn test_grid_dimensions_without_columns() {
+        let mut grid: Grid<u8> = Grid::new(0, 4);
+
+        assert_eq!((grid.width(), grid.height()), (0, 4));
+        assert_eq!(grid.iter().count(), 0);
+
+        grid.grow_width(2);
+
+        assert_eq!((grid.width(), grid.height()), (2, 4));
+        assert_eq!(grid.iter().count(), 8);
+
+        let grid = Grid::from_lines("\n\n", |_| 0u8);
+        assert_eq!((grid.width(), grid.height()), (0, 2));
+    }
+
+    #[test]
+    fn test_grid_get_signed() {
+        let mut grid: Grid<u8> = Grid::new(3, 2);
+        *grid.point_mut(&Point { x: 2, y: 1 }) = 5;
+
+        assert_eq!(grid.get_signed(2, 1), Some(&5));
+        assert_eq!(grid.get_signed(0, 0), Some(&0));
+        assert_eq!(grid.get_signed(-1, 0), None);
+        assert_eq!(grid.get_signed(0, -1), None);
+        assert_eq!(grid.get_signed(3, 0), None);
+        assert_eq!(grid.get_signed(0, 2), None);
+    }
+
+    #[test]
+    fn test_grid_is_out_of_bounds() {
+        let grid: Grid<u8> = Grid::new(3, 2);
+
+        assert!(!grid.is_out_of_bounds(&Point { x: 0, y: 0 }));
+        assert!(!grid.is_out_of_bounds(&Point { x: 2, y: 1 }));
+
+        // wide enough to wrap onto the next row if only the index were checked
+        assert!(grid.is_out_of_bounds(&Point { x: 3, y: 0 }));
+        assert!(grid.is_out_of_bounds(&Point { x: 5, y: 0 }));
+        assert!(grid.is_out_of_bounds(&Point { x: 0, y: 2 }));
+        assert!(grid.is_out_of_bounds(&Point { x: 3, y: 1 }));
+    }
+
+    #[test]
+    fn test_grid_get() {
+        let mut grid: Grid<u8> = Grid::new(3, 2);
+
+        *grid.get_mut(&Point { x: 1, y: 1 }).unwrap() = 7;
+
+        assert_eq!(grid.get(&Point { x: 1, y: 1 }), Some(&7));
+        assert_eq!(grid.get(&Point { x: 2, y: 0 }), Some(&0));
+        assert_eq!(grid.get(&Point { x: 3, y: 0 }), None);
+        assert_eq!(grid.get(&Point { x: 0, y: 2 }), None);
+        assert!(grid.get_mut(&Point { x: 3, y: 1 }).is_none());
+        assert!(grid.get_mut(&Point { x: 0, y: 2 }).is_none());
+    }
+
+    #[test]
+    fn test_grid_iter_mut() {
+        let mut grid: Grid<u8> = Grid::new(3, 2);
+
+        grid.iter_mut().for_each(|value| *value += 2);
+
+        assert!(grid.iter().all(|&value| value == 2));
+
+        for (value, n) in grid.iter_mut().zip(1..) {
+            *value = n;
+        }
+
+        assert_eq!(grid.render(), "123\n456");
+    }
+
+    #[test]
+    fn test_grid_enumerate_mut() {
+        let mut grid: Grid<usize> = Grid::new(3, 2);
+
+        for (point, value) in grid.enumerate_mut() {
+            *value = point.x * 10 + point.y;
+        }
+
+        assert_eq!(grid.render(), "01020\n11121");
+        assert_eq!(grid[Point { x: 2, y: 1 }], 21);
+    }
+
+    #[test]
+    fn test_grid_enumerate() {
+        let mut grid: Grid<u8> = Grid::new(3, 2);
+        *grid.point_mut(&Point { x: 1, y: 1 }) = 9;
+
+        let points: Vec<(usize, usize)> = grid.enumerate().map(|(p, _)| (p.x, p.y)).collect();
+
+        assert_eq!(points, vec![(0, 0), (1, 0), (2, 0), (0, 1), (1, 1), (2, 1)]);
+        assert_eq!(
+            grid.enumerate()
+                .find(|&(_, &value)| value == 9)
+                .map(|(p, _)| (p.x, p.y)),
+            Some((1, 1))
+        );
+    }
+
+    #[test]
+    fn test_grid_from_lines() {
+        let grid = Grid::from_lines("ab\nabcd\n\na\n", |ch| ch as u8 - b'a' + 1);
+
+        assert_eq!(grid.width(), 4);
+        assert_eq!(grid.height(), 4);
+        assert_eq!(
+            grid.iter().copied().collect::<Vec<u8>>(),
+            vec![1, 2, 0, 0, 1, 2, 3, 4, 0, 0, 0, 0, 1, 0, 0, 0]
+        );
+    }
+
+    #[test]
+    fn test_grid_from_rows() {
+        let grid = Grid::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.render(), "123\n456");
+
+        let empty: Grid<u8> = Grid::from_rows(vec![]).unwrap();
+        assert_eq!((empty.width(), empty.height()), (0, 0));
+
+        let ragged = Grid::from_rows(vec![vec![1, 2], vec![3, 4], vec![5]]);
+        assert_eq!(
+            ragged,
+            Err(GridError::RaggedRow {
+                row: 2,
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(
+            ragged.unwrap_err().to_string(),
+            "row 2 of the grid has 1 values, but the rows before it have 2"
+        );
+    }
+
+    #[test]
+    fn test_grid_from_str_with() {
+        let digit = |ch: char| ch.to_digit(10).ok_or(ch);
+
+        let grid: Grid<u32> = Grid::from_str_with("12\n3\n456", digit).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 3));
+        assert_eq!(grid.render(), "120\n300\n456");
+
+        // the first bad character is reported, even with more to come
+        assert_eq!(Grid::from_str_with("12\n3x\n4y", digit).err(), Some('x'));
+    }
+
+    #[test]
+    fn test_grid_neighbours() {
+        let grid: Grid<u8> = Grid::new(3, 3);
+        let coords = |points: &mut dyn Iterator<Item = Point>| -> Vec<(usize, usize)> {
+            points.map(|p| (p.x, p.y)).collect()
+        };
+
+        let corner = Point { x: 0, y: 0 };
+        assert_eq!(
+            coords(&mut grid.orthogonal_neighbours(&corner)),
+            vec![(1, 0), (0, 1)]
+        );
+        assert_eq!(
+            coords(&mut grid.all_neighbours(&corner)),
+            vec![(1, 0), (1, 1), (0, 1)]
+        );
+
+        let far_corner = Point { x: 2, y: 2 };
+        assert_eq!(
+            coords(&mut grid.orthogonal_neighbours(&far_corner)),
+            vec![(2, 1), (1, 2)]
+        );
+        assert_eq!(grid.all_neighbours(&far_corner).count(), 3);
+
+        let edge = Point { x: 1, y: 0 };
+        assert_eq!(
+            coords(&mut grid.neighbours4(edge).into_iter()),
+            vec![(2, 0), (1, 1), (0, 0)]
+        );
+        assert_eq!(grid.neighbours8(edge).len(), 5);
+
+        let centre = Point { x: 1, y: 1 };
+        assert_eq!(
+            coords(&mut grid.orthogonal_neighbours(&centre)),
+            vec![(1, 0), (2, 1), (1, 2), (0, 1)]
+        );
+        assert_eq!(
+            coords(&mut grid.all_neighbours(&centre)),
+            vec![
+                (0, 0),
+                (1, 0),
+                (2, 0),
+                (2, 1),
+                (2, 2),
+                (1, 2),
+                (0, 2),
+                (0, 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_grid_neighbours_adjacency() {
+        let grid: Grid<u8> = Grid::new(4, 3);
+
+        let adjacency: HashMap<Point, Vec<Point>> = grid
+            .enumerate()
+            .map(|(point, _)| (point, grid.neighbours4(point)))
+            .collect();
+
+        // 4 corners with 2 neighbours, 6 edges with 3 and 2 in the middle with 4
+        assert_eq!(adjacency.len(), 12);
+        assert_eq!(
+            adjacency.values().map(Vec::len).sum::<usize>(),
+            4 * 2 + 6 * 3 + 2 * 4
+        );
+
+        // and with the diagonals, 3, 5 and 8
+        assert_eq!(
+            grid.enumerate()
+                .map(|(point, _)| grid.neighbours8(point).len())
+                .sum::<usize>(),
+            4 * 3 + 6 * 5 + 2 * 8
+        );
+    }
+
+    #[test]
+    fn test_grid_render() {
+        let mut grid: Grid<u8> = Grid::new(3, 2);
+        *grid.point_mut(&Point { x: 0, y: 1 }) = 4;
+        *grid.point_mut(&Point { x: 2, y: 0 }) = 7;
+
+        assert_eq!(grid.render(), "007\n400");
+        assert_eq!(format!("{}", grid), "007\n400\n");
+
+        let grid = Grid::from_lines("#.o\n.#.", |ch| ch);
+        assert_eq!(format!("{}", grid), "#.o\n.#.\n");
+    }
+
+    #[test]
+    fn test_grid_grow() {
+        let mut grid: Grid<u8> = Grid::new(3, 2);
+        *grid.point_mut(&Point { x: 0, y: 0 }) = 1;
+        *grid.point_mut(&Point { x: 2, y: 0 }) = 2;
+        *grid.point_mut(&Point { x: 1, y: 1 }) = 3;
+
+        grid.grow_width(2);
+
+        assert_eq!((grid.width(), grid.height()), (5, 2));
+        assert_eq!(grid.render(), "10200\n03000");
+
+        grid.grow_height(1);
+        *grid.point_mut(&Point { x: 4, y: 2 }) = 4;
+
+        assert_eq!((grid.width(), grid.height()), (5, 3));
+        assert_eq!(grid.render(), "10200\n03000\n00004");
+    }
+
+    #[test]
+    fn test_grid_rows_and_cols() {
+        let grid = Grid::from_lines("123\n456\n789", |ch| ch.to_digit(10).unwrap());
+
+        assert_eq!(grid.row(1), &[4, 5, 6]);
+        assert_eq!(grid.col(1).copied().collect::<Vec<_>>(), vec![2, 5, 8]);
+        assert_eq!(
+            grid.col(2).rev().copied().collect::<Vec<_>>(),
+            vec![9, 6, 3]
+        );
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            vec![&[1, 2, 3], &[4, 5, 6], &[7, 8, 9]]
+        );
+    }
+
+    #[test]
+    fn test_grid_transpose() {
+        let grid = Grid::from_lines("123\n456", |ch| ch.to_digit(10).unwrap());
+        let transposed = grid.transpose();
+
+        assert_eq!((transposed.width(), transposed.height()), (2, 3));
+        assert_eq!(transposed.render(), "14\n25\n36");
+        assert_eq!(transposed.transpose(), grid);
+
+        let empty: Grid<u8> = Grid::new(0, 3);
+        assert_eq!(
+            (empty.transpose().width(), empty.transpose().height()),
+            (3, 0)
+        );
+    }
+
+    #[test]
+    fn test_grid_flood_fill() {
+        let grid = Grid::from_lines("..#..\n..#..\n.##..\n..#..", |ch| ch == '.');
+        let coords = |points: HashSet<Point>| {
+            let mut coords: Vec<(usize, usize)> = points.iter().map(|p| (p.x, p.y)).collect();
+            coords.sort();
+            coords
+        };
+
+        let left = grid.flood_fill(Point { x: 0, y: 0 }, |&open| open);
+        assert_eq!(
+            coords(left),
+            vec![(0, 0), (0, 1), (0, 2), (0, 3), (1, 0), (1, 1), (1, 3)]
+        );
+
+        let right = grid.flood_fill(Point { x: 4, y: 3 }, |&open| open);
+        assert_eq!(right.len(), 8);
+        assert!(!right.contains(&Point { x: 1, y: 0 }));
+
+        assert!(grid
+            .flood_fill(Point { x: 2, y: 0 }, |&open| open)
+            .is_empty());
+        assert!(grid
+            .flood_fill(Point { x: 5, y: 0 }, |&open| open)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_grid_expand() {
+        let mut grid: Grid<u8> = Grid::new(2, 2);
+        *grid.point_mut(&Point { x: 0, y: 0 }) = 1;
+        *grid.point_mut(&Point { x: 1, y: 1 }) = 2;
+
+        assert_eq!(grid.expand(2, 1, 1), 2);
+        assert_eq!((grid.width(), grid.height()), (5, 3));
+        assert_eq!(grid.render(), "00100\n00020\n00000");
+        assert_eq!(grid[Point { x: 3, y: 1 }], 2);
+
+        assert_eq!(grid.expand(0, 0, 0), 0);
+        assert_eq!(grid.render(), "00100\n00020\n00000");
+    }
+
+    #[test]
+    fn test_grid_positions_of() {
+        let grid = Grid::from_lines("#..\n.##\n", |ch| ch == '#');
+
+        assert_eq!(
+            grid.positions_of(|&wall| wall)
+                .map(|p| (p.x, p.y))
+                .collect::<Vec<(usize, usize)>>(),
+            vec![(0, 0), (1, 1), (2, 1)]
+        );
+        assert_eq!(grid.positions_of(|_| false).count(), 0);
+    }
+
+    #[test]
+    fn test_grid_count() {
+        let grid = Grid::from_lines("#..\n.##\n", |ch| ch == '#');
+
+        assert_eq!(grid.count(|&wall| wall), 3);
+        assert_eq!(grid.count(|&wall| !wall), 3);
+        assert_eq!(grid.count(|_| true), 6);
+    }
+
+    #[test]
+    fn test_grid_index() {
+        let mut grid: Grid<u8> = Grid::new(3, 2);
+
+        grid[Point { x: 2, y: 1 }] = 6;
+        grid[Point { x: 0, y: 1 }] += 1;
+
+        assert_eq!(grid[Point { x: 2, y: 1 }], 6);
+        assert_eq!(grid[Point { x: 0, y: 1 }], 1);
+        assert_eq!(grid[Point { x: 1, y: 1 }], 0);
+        assert_eq!(grid.point(&Point { x: 2, y: 1 }), &6);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_grid_index_out_of_bounds() {
+        let grid: Grid<u8> = Grid::new(3, 2);
+
+        let _ = grid[Point { x: 0, y: 2 }];
+    }
+
+    #[test]
+    fn test_grid_wrapping_point() {
+        let grid = Grid::from_lines("abc\ndef\n", |ch| ch);
+
+        assert_eq!(grid.wrapping_point(-1, 0), &'c');
+        assert_eq!(grid.wrapping_point(0, -1), &'d');
+        assert_eq!(grid.wrapping_point(3, 1), &'d');
+        assert_eq!(grid.wrapping_point(1, 2), &'b');
+        assert_eq!(grid.wrapping_point(-4, -3), &'f');
+        assert_eq!(grid.wrapping_point(1, 1), &'e');
+    }
+
+    #[test]
+    fn test_grid_map() {
+        let digits = Grid::from_lines("120\n305\n", |ch| ch.to_digit(10).unwrap() as u8);
+        let nonzero = digits.map(|&digit| digit != 0);
+
+        assert_eq!((nonzero.width(), nonzero.height()), (3, 2));
+        assert_eq!(nonzero.render(), "truetruefalse\ntruefalsetrue");
+        assert_eq!(nonzero.count(|&x| x), 4);
+
+        // every value lands at the same point it came from
+        let digits: Grid<u32> = digits.map(|&digit| digit as u32);
+        let positive = digits.map(|&x| x > 0);
+        assert!(digits
+            .enumerate()
+            .all(|(point, &digit)| positive[point] == (digit > 0)));
+    }
+
+    #[test]
+    fn test_point_translate() {
+        let p = Point { x: 3, y: 0 };
+
+        assert_eq!(p.translate(-1, 2), Some(Point { x: 2, y: 2 }));
+        assert_eq!(p.translate(0, 0), Some(p));
+        assert_eq!(p.translate(-3, 0), Some(Point { x: 0, y: 0 }));
+        assert_eq!(p.translate(-4, 0), None);
+        assert_eq!(p.translate(0, -1), None);
+        assert_eq!(
+            Point {
+                x: usize::MAX,
+                y: 0
+            }
+            .translate(1, 0),
+            None
+        );
+    }
+
+    #[test]
+    fn test_point_arithmetic() {
+        let a = Point { x: 3, y: 5 };
+        let b = Point { x: 1, y: 2 };
+
+        assert_eq!(a + b, Point { x: 4, y: 7 });
+        assert_eq!(a - b, Point { x: 2, y: 3 });
+        assert_eq!(a.checked_sub(&b), Some(Point { x: 2, y: 3 }));
+        assert_eq!(a.checked_sub(&a), Some(Point { x: 0, y: 0 }));
+    }
+
+    #[test]
+    fn test_point_manhattan() {
+        let a = Point { x: 3, y: 5 };
+        let b = Point { x: 7, y: 1 };
+
+        assert_eq!(a.manhattan(&b), 8);
+        assert_eq!(b.manhattan(&a), 8);
+        assert_eq!(a.manhattan(&a), 0);
+    }
+
+    #[test]
+    fn test_point_underflow() {
+        let a = Point { x: 0, y: 5 };
+
+        assert_eq!(a.checked_sub(&Point { x: 1, y: 0 }), None);
+        assert_eq!(a.checked_sub(&Point { x: 0, y: 6 }), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_point_sub_underflow_panics() {
+        let _ = Point { x: 0, y: 0 } - Point { x: 1, y: 0 };
+    }
+
+    #[test]
+    fn test_point_from_str() {
+        assert_eq!("498,4".parse::<Point>().unwrap(), Point { x: 498, y: 4 });
+        assert_eq!(Point::from("0,12"), Point { x: 0, y: 12 });
+    }
+
+    #[test]
+    fn test_point_parse_errors() {
+        let err = "5".parse::<Point>().unwrap_err();
+        assert_eq!(err.component, "comma");
+        assert_eq!(
+            format!("{}", err),
+            "error parsing point \"5\": missing or invalid comma"
+        );
+
+        assert_eq!("5,".parse::<Point>().unwrap_err().component, "y coordinate");
+        assert_eq!(
+            "a,5".parse::<Point>().unwrap_err().component,
+            "x coordinate"
+        );
+        assert_eq!(
+            "-1,5".parse::<Point>().unwrap_err().component,
+            "x coordinate"
+        );
+    }
+
+    #[test]
+    fn test_direction_rotations() {
+        use Direction::*;
+
+        for (from, clockwise, anticlockwise, opposite) in [
+            (North, East, West, South),
+            (East, South, North, West),
+            (South, West, East, North),
+            (West, North, South, East),
+        ] {
+            assert_eq!(from.turn_clockwise(), clockwise);
+            assert_eq!(from.turn_anticlockwise(), anticlockwise);
+            assert_eq!(from.opposite(), opposite);
+            assert_eq!(from.turn_clockwise().turn_anticlockwise(), from);
+            assert_eq!(Direction::from_index(from as usize + 4), from);
+        }
+    }
+
+    #[test]
+    fn test_direction_full_turns() {
+        for from in (0..4).map(Direction::from_index) {
+            let clockwise = (0..4).fold(from, |direction, _| direction.turn_clockwise());
+            let anticlockwise = (0..4).fold(from, |direction, _| direction.turn_anticlockwise());
+
+            assert_eq!(clockwise, from);
+            assert_eq!(anticlockwise, from);
+            assert_eq!(from.opposite().opposite(), from);
+        }
+    }
+
+    #[test]
+    fn test_direction_delta() {
+        assert_eq!(Direction::North.delta(), (0, -1));
+        assert_eq!(Direction::East.delta(), (1, 0));
+        assert_eq!(Direction::South.delta(), (0, 1));
+        assert_eq!(Direction::West.delta(), (-1, 0));
+    }
+
+    #[test]
+    fn test_direction_try_from() {
+        for (chars, direction) in [
+            ("NU^", Direction::North),
+            ("ER>", Direction::East),
+            ("SDv", Direction::South),
+            ("WL<", Direction::West),
+        ] {
+            for ch in chars.chars() {
+                assert_eq!(Direction::try_from(ch).unwrap(), direction);
+            }
+        }
+
+        assert!(Direction::try_from('x').is_err());
+    }
+
+    #[test]
+    fn test_ipoint_arithmetic() {
+        let a = IPoint { x: 3, y: -2 };
+        let b = IPoint { x: -5, y: 4 };
+
+        assert_eq!(a + b, IPoint { x: -2, y: 2 });
+        assert_eq!(a - b, IPoint { x: 8, y: -6 });
+        assert_eq!(b - b, IPoint::default());
+        assert_eq!(a.manhattan(&b), 14);
+        assert_eq!(b.manhattan(&a), 14);
+        assert_eq!(a.manhattan(&a), 0);
+    }
+
+    #[test]
+    fn test_ipoint_from_str() {
+        assert_eq!("3,-2".parse::<IPoint>().unwrap(), IPoint { x: 3, y: -2 });
+        assert_eq!("-10, 4".parse::<IPoint>().unwrap(), IPoint { x: -10, y: 4 });
+
+        assert!("3".parse::<IPoint>().is_err());
+        assert!("3,".parse::<IPoint>().is_err());
+        assert!("3,y".parse::<IPoint>().is_err());
+    }
+
+    #[test]
+    fn test_ipoint_neighbours() {
+        let coords = |points: &[IPoint]| -> Vec<(isize, isize)> {
+            points.iter().map(|p| (p.x, p.y)).collect()
+        };
+        let origin = IPoint::default();
+
+        assert_eq!(
+            coords(&origin.neighbours4()),
+            vec![(0, -1), (1, 0), (0, 1), (-1, 0)]
+        );
+        assert_eq!(
+            coords(&origin.neighbours8()),
+            vec![
+                (-1, -1),
+                (0, -1),
+                (1, -1),
+                (1, 0),
+                (1, 1),
+                (0, 1),
+                (-1, 1),
+                (-1, 0)
+            ]
+        );
+
+        // the same shape as Grid gives for a cell with room all around
+        let grid: Grid<u8> = Grid::new(3, 3);
+        let centre = IPoint { x: 1, y: 1 };
+        assert_eq!(
+            coords(&centre.neighbours8()),
+            grid.all_neighbours(&Point { x: 1, y: 1 })
+                .map(|p| (p.x as isize, p.y as isize))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_bfs() {
+        //  a - b - c - d
+        //   \         /
+        //    e ----- f
+        let edges = HashMap::from([
+            ('a', vec!['b', 'e']),
+            ('b', vec!['a', 'c']),
+            ('c', vec!['b', 'd']),
+            ('d', vec!['c', 'f']),
+            ('e', vec!['a', 'f']),
+            ('f', vec!['e', 'd']),
+            ('g', vec![]),
+        ]);
+        let neighbours = |node: &char| edges[node].clone();
+
+        assert_eq!(
+            bfs('a', neighbours, |&node| node == 'd'),
+            Some((3, vec!['a', 'b', 'c', 'd']))
+        );
+        assert_eq!(
+            bfs('a', neighbours, |&node| node == 'f'),
+            Some((2, vec!['a', 'e', 'f']))
+        );
+        assert_eq!(
+            bfs('c', neighbours, |&node| node == 'c'),
+            Some((0, vec!['c']))
+        );
+        assert_eq!(bfs('a', neighbours, |&node| node == 'g'), None);
+    }
+
+    #[test]
+    fn test_astar() {
+        let edges = HashMap::from([
+            ('a', vec![('b', 1), ('c', 2)]),
+            ('b', vec![('d', 5)]),
+            ('c', vec![('b', 1), ('d', 2)]),
+            ('d', vec![('e', 1)]),
+        ]);
+        let neighbours = |node: &char| edges.get(node).cloned().unwrap_or_default();
+
+        assert_eq!(
+            astar(['a'], neighbours, |_| 0, |&node| node == 'e'),
+            Some((5, vec!['a', 'c', 'd', 'e']))
+        );
+
+        // starting anywhere along the way picks the nearest start
+        assert_eq!(
+            astar(['a', 'c'], neighbours, |_| 0, |&node| node == 'd'),
+            Some((2, vec!['c', 'd']))
+        );
+
+        // a start which is already at the goal costs nothing
+        assert_eq!(
+            astar(['d'], neighbours, |_| 0, |&node| node == 'd'),
+            Some((0, vec!['d']))
+        );
+
+        assert_eq!(astar(['e'], neighbours, |_| 0, |&node| node == 'a'), None);
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        // the direct road is expensive, so the way round through the villages is cheaper
+        let edges = HashMap::from([
+            ("home", vec![("town", 10), ("village", 2)]),
+            ("village", vec![("hamlet", 3), ("home", 2)]),
+            ("hamlet", vec![("town", 4)]),
+            ("town", vec![]),
+        ]);
+        let neighbours = |node: &&str| edges[node].clone();
+
+        assert_eq!(
+            dijkstra("home", neighbours, |&node| node == "town"),
+            Some((9, vec!["home", "village", "hamlet", "town"]))
+        );
+        assert_eq!(
+            dijkstra("home", neighbours, |&node| node == "home"),
+            Some((0, vec!["home"]))
+        );
+        assert_eq!(dijkstra("town", neighbours, |&node| node == "home"), None);
+    }
+
+    #[test]
+    fn test_astar_heuristic() {
+        // walking around a wall on a 5x5 grid, guided by the manhattan distance
+        let wall = |p: &IPoint| p.x == 2 && p.y < 4;
+        let neighbours = |p: &IPoint| {
+            [(0, -1), (1, 0), (0, 1), (-1, 0)]
+                .into_iter()
+                .map(|(x, y)| *p + IPoint { x, y })
+                .filter(|p| (0..5).contains(&p.x) && (0..5).contains(&p.y) && !wall(p))
+                .map(|p| (p, 1))
+                .collect::<Vec<_>>()
+        };
+
+        let goal = IPoint { x: 4, y: 0 };
+        let (cost, path) = astar(
+            [IPoint { x: 0, y: 0 }],
+            neighbours,
+            |p| p.manhattan(&goal) as u32,
+            |p| *p == goal,
+        )
+        .unwrap();
+
+        assert_eq!(cost, 12);
+        assert_eq!(path.len(), 13);
+        assert!(path.iter().all(|p| !wall(p)));
+    }
+
+    #[test]
+    fn test_astar_matches_bfs() {
+        // a small maze, searched from several starts at once
+        let grid = Grid::from_lines(".....\n.###.\n...#.\n##.#.\n.....", |ch| ch == '.');
+        let open = |p: &Point| grid[*p];
+        let goal = Point { x: 0, y: 2 };
+
+        // the nearest of several starts wins
+        let starts = [
+            Point { x: 4, y: 0 },
+            Point { x: 0, y: 4 },
+            Point { x: 4, y: 4 },
+        ];
+        let multi = astar(
+            starts,
+            |p| grid.orthogonal_neighbours(p).filter(open).map(|n| (n, 1)),
+            |p| p.manhattan(&goal) as u32,
+            |p| *p == goal,
+        )
+        .map(|(cost, _)| cost);
+        let nearest = starts
+            .iter()
+            .filter_map(|&start| {
+                bfs(
+                    start,
+                    |p| {
+                        grid.orthogonal_neighbours(p)
+                            .filter(open)
+                            .collect::<Vec<_>>()
+                    },
+                    |p| *p == goal,
+                )
+            })
+            .map(|(steps, _)| steps as u32)
+            .min();
+        assert_eq!(multi, nearest);
+        assert_eq!(multi, Some(6));
+
+        // and each start on its own agrees with the brute force too
+        let starts: Vec<Point> = grid.positions_of(|&open| open).collect();
+
+        for start in starts {
+            let expected = bfs(
+                start,
+                |p| {
+                    grid.orthogonal_neighbours(p)
+                        .filter(open)
+                        .collect::<Vec<_>>()
+                },
+                |p| *p == goal,
+            )
+            .map(|(steps, _)| steps as u32);
+
+            let found = astar(
+                [start],
+                |p| grid.orthogonal_neighbours(p).filter(open).map(|n| (n, 1)),
+                |p| p.manhattan(&goal) as u32,
+                |p| *p == goal,
+            )
+            .map(|(cost, _)| cost);
+
+            assert_eq!(found, expected, "from {:?}", start);
+        }
+    }
+
+    #[test]
+    fn test_bfs_distances() {
+        // two starts in opposite corners of a maze, with a wall cutting off the bottom-left cell
+        let grid = Grid::from_lines("..#..\n.....\n#.#..\n.#...", |ch| ch == '.');
+        let starts = [Point { x: 0, y: 0 }, Point { x: 4, y: 3 }];
+
+        let distances = bfs_distances(starts, |p| {
+            grid.orthogonal_neighbours(p)
+                .filter(|n| grid[*n])
+                .collect::<Vec<_>>()
+        });
+
+        let expected = [
+            "01#43", //
+            "12332", //
+            "#3#21", //
+            ".#210",
+        ];
+        for (y, row) in expected.iter().enumerate() {
+            for (x, ch) in row.chars().enumerate() {
+                let found = distances.get(&Point { x, y }).copied();
+                assert_eq!(found, ch.to_digit(10), "at ({}, {})", x, y);
+            }
+        }
+
+        assert!(bfs_distances([], |_: &Point| Vec::new()).is_empty());
+    }
+
+    #[test]
+    fn test_index_conversions() {
+        let width = 4;
+
+        for (i, point) in [
+            (0, Point { x: 0, y: 0 }),
+            (3, Point { x: 3, y: 0 }),
+            (4, Point { x: 0, y: 1 }),
+            (7, Point { x: 3, y: 1 }),
+            (8, Point { x: 0, y: 2 }),
+        ] {
+            assert_eq!(index_to_point(width, i), point, "index {}", i);
+            assert_eq!(point_to_index(width, &point), i, "{:?}", point);
+        }
+
+        assert_eq!(index_to_point(1, 5), Point { x: 0, y: 5 });
+    }
+
+    #[test]
+    fn test_parse_char_grid() {
+        let (grid, width) = parse_char_grid("abc\ndef");
+
+        assert_eq!(width, 3);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid[index_to_point(width, 2)], 'c');
+        assert_eq!(grid[index_to_point(width, 3)], 'd');
+        assert_eq!(
+            grid.enumerate()
+                .map(|(point, _)| point_to_index(width, &point))
+                .collect::<Vec<_>>(),
+            (0..6).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_sparse_grid_bounds() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.bounds(), None);
+        assert_eq!(grid.render('.'), "");
+
+        grid.set(Point { x: 5, y: 3 }, 'a');
+        assert_eq!(
+            grid.bounds(),
+            Some((Point { x: 5, y: 3 }, Point { x: 5, y: 3 }))
+        );
+
+        grid.set(Point { x: 3, y: 4 }, 'b');
+        grid.set(Point { x: 7, y: 2 }, 'c');
+        assert_eq!(
+            grid.bounds(),
+            Some((Point { x: 3, y: 2 }, Point { x: 7, y: 4 }))
+        );
+
+        // points inside the bounds don't move them
+        assert_eq!(grid.set(Point { x: 5, y: 3 }, 'd'), Some('a'));
+        assert_eq!(
+            grid.bounds(),
+            Some((Point { x: 3, y: 2 }, Point { x: 7, y: 4 }))
+        );
+
+        assert_eq!(grid.len(), 3);
+        assert!(grid.contains(&Point { x: 7, y: 2 }));
+        assert!(!grid.contains(&Point { x: 4, y: 2 }));
+        assert_eq!(grid.get(&Point { x: 5, y: 3 }), Some(&'d'));
+        assert_eq!(grid.render('.'), "....c\n..d..\nb....");
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = ParseError::new("op", '%');
+
+        assert_eq!(err, ParseError::new("op", "%"));
+        assert_eq!(err.to_string(), "error parsing op \"%\"");
+    }
+
+    #[test]
+    fn test_top_n() {
+        let values = [4000, 11000, 24000, 6000, 10000];
+
+        assert_eq!(top_n(&values, 3), 45000);
+        assert_eq!(top_n(&values, 1), 24000);
+        assert_eq!(top_n(&values, 0), 0);
+        assert_eq!(top_n(&values, 10), 55000);
+        assert_eq!(top_n(&[], 3), 0);
 
-        write!(f, "{}", s)
+        // duplicates each count
+        assert_eq!(top_n(&[5, 9, 9, 1], 2), 18);
     }
 }
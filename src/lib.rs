@@ -3,51 +3,194 @@
  * There is no need to edit this file unless you want to change template functionality.
  * Prefer `./helpers.rs` if you want to extract code from your solutions.
  */
-use std::env;
+use std::fmt::Display;
 use std::fs;
+use std::io::{self, IsTerminal, Read};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+// Lets the solutions, which are written as binaries against this crate, be built as part of it too.
+extern crate self as advent_of_code;
 
 pub mod helpers;
+// Left out of the library's own unit tests, which would otherwise run every day's tests a second
+// time over. The solutions are tested through the library from tests/solutions.rs instead.
+#[cfg(not(test))]
+pub mod solutions;
+
+#[cfg(not(test))]
+pub use solutions::{bench, bench_with};
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
+// Prints a part's answer along with how long it took. This is only ever called from the days' main
+// functions, which tests don't run, so the timings can't end up in test output and there's no need
+// to leave them out under cfg(test).
 #[macro_export]
 macro_rules! solve {
     ($part:expr, $solver:ident, $input:expr) => {{
-        use advent_of_code::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
-        use std::fmt::Display;
+        use advent_of_code::{format_result, Answer, ANSI_BOLD, ANSI_RESET};
         use std::time::Instant;
 
-        fn print_result<T: Display>(func: impl FnOnce(&str) -> Option<T>, input: &str) {
+        fn print_result<T: Answer>(func: impl FnOnce(&str) -> Option<T>, input: &str) {
             let timer = Instant::now();
             let result = func(input);
             let elapsed = timer.elapsed();
-            match result {
-                Some(result) => {
-                    println!(
-                        "{} {}(elapsed: {:.2?}){}",
-                        result, ANSI_ITALIC, elapsed, ANSI_RESET
-                    );
-                }
-                None => {
-                    println!("not solved.")
-                }
-            }
+
+            println!("{}", format_result(result, elapsed));
         }
 
         println!("🎄 {}Part {}{} 🎄", ANSI_BOLD, $part, ANSI_RESET);
-        print_result($solver, $input);
+        match $input {
+            Ok(input) => print_result($solver, input),
+            Err(err) => eprintln!("{}", err),
+        }
     }};
 }
 
-pub fn read_file(folder: &str, day: u8) -> String {
-    let cwd = env::current_dir().unwrap();
+// Anything a part can return as its answer. It's implemented for the types that answers come in
+// rather than for everything Display, so that types which aren't Display, like tuples, can be
+// answers too.
+pub trait Answer {
+    fn render(&self) -> String;
+}
+
+macro_rules! display_answers {
+    ($($answer:ty),*) => {
+        $(
+            impl Answer for $answer {
+                fn render(&self) -> String {
+                    self.to_string()
+                }
+            }
+        )*
+    };
+}
+
+display_answers!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, char, str, String);
+
+impl<T: Answer + ?Sized> Answer for &T {
+    fn render(&self) -> String {
+        (*self).render()
+    }
+}
+
+// Pairs are written the way the puzzles ask for them to be entered, e.g. coordinates as "x,y".
+impl<A: Answer, B: Answer> Answer for (A, B) {
+    fn render(&self) -> String {
+        format!("{},{}", self.0.render(), self.1.render())
+    }
+}
+
+// How solve! reports the outcome of a part. Days which aren't finished yet return None for the parts
+// still to do, so say so rather than printing nothing useful. None means only that: a part which
+// fails on its input panics with the reason instead.
+pub fn format_result<T: Answer>(result: Option<T>, elapsed: Duration) -> String {
+    match result {
+        Some(result) => format!(
+            "{} {}(elapsed: {:.2?}){}",
+            result.render(),
+            ANSI_ITALIC,
+            elapsed,
+            ANSI_RESET
+        ),
+        None => "not yet implemented".to_string(),
+    }
+}
 
-    let filepath = cwd.join("src").join(folder).join(format!("{:02}.txt", day));
+#[derive(Debug)]
+pub struct ReadFileError {
+    path: PathBuf,
+    source: io::Error,
+    // What the file was for, if known, so a missing file can be explained.
+    description: Option<String>,
+}
 
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+impl Display for ReadFileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.description {
+            Some(description) if self.source.kind() == io::ErrorKind::NotFound => {
+                write!(f, "{} missing: create {}", description, self.path.display())
+            }
+            _ => write!(f, "could not read {}: {}", self.path.display(), self.source),
+        }
+    }
+}
+
+impl std::error::Error for ReadFileError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+pub fn read_file(folder: &str, day: u8) -> Result<String, ReadFileError> {
+    read_file_from(
+        Path::new("src")
+            .join(folder)
+            .join(format!("{:02}.txt", day)),
+    )
+}
+
+// Reads a puzzle input from anywhere, for when it doesn't live in the usual src/inputs/NN.txt.
+pub fn read_file_from(path: impl AsRef<Path>) -> Result<String, ReadFileError> {
+    let path = path.as_ref();
+
+    fs::read_to_string(path).map_err(|source| ReadFileError {
+        path: path.to_path_buf(),
+        source,
+        description: None,
+    })
+}
+
+pub fn read_example(day: u8) -> Result<String, ReadFileError> {
+    read_file("examples", day).map_err(|err| ReadFileError {
+        description: Some(format!("example for day {}", day)),
+        ..err
+    })
+}
+
+pub fn read_input(day: u8) -> Result<String, ReadFileError> {
+    read_file("inputs", day).map_err(|err| ReadFileError {
+        description: Some(format!("input for day {}", day)),
+        ..err
+    })
+}
+
+// Reads a puzzle input piped in on stdin, e.g. `cat input.txt | cargo solve 12`.
+pub fn read_stdin() -> io::Result<String> {
+    read_from(io::stdin().lock())
+}
+
+pub fn read_from(mut reader: impl Read) -> io::Result<String> {
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+    Ok(input)
+}
+
+// What the days' binaries run against. src/inputs/NN.txt wins if it exists; otherwise, whatever is
+// piped in on stdin is used instead. With neither, the error about the missing input file stands.
+pub fn read_input_or_stdin(day: u8) -> Result<String, ReadFileError> {
+    let stdin = io::stdin();
+    let piped = !stdin.is_terminal();
+
+    read_input_or(day, stdin.lock(), piped)
+}
+
+fn read_input_or(day: u8, stdin: impl Read, piped: bool) -> Result<String, ReadFileError> {
+    read_input(day).or_else(|err| {
+        if !piped || err.source.kind() != io::ErrorKind::NotFound {
+            return Err(err);
+        }
+
+        // Nothing usable having been piped in isn't worth its own error; the input file is still
+        // what's missing.
+        match read_from(stdin) {
+            Ok(input) if !input.is_empty() => Ok(input),
+            _ => Err(err),
+        }
+    })
 }
 
 fn parse_time(val: &str, postfix: &str) -> f64 {
@@ -95,6 +238,142 @@ macro_rules! assert_approx_eq {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_format_result() {
+        assert_eq!(
+            format_result(Some(42), Duration::from_millis(3)),
+            format!("42 {}(elapsed: 3.00ms){}", ANSI_ITALIC, ANSI_RESET)
+        );
+        assert_eq!(
+            format_result(None::<u32>, Duration::from_millis(3)),
+            "not yet implemented"
+        );
+    }
+
+    #[test]
+    fn test_format_result_tuple() {
+        fn part(input: &str) -> Option<(u32, i64)> {
+            let (x, y) = input.split_once(' ')?;
+            Some((x.parse().ok()?, y.parse().ok()?))
+        }
+
+        assert_eq!(
+            format_result(part("12 -3"), Duration::from_millis(3)),
+            format!("12,-3 {}(elapsed: 3.00ms){}", ANSI_ITALIC, ANSI_RESET)
+        );
+        assert_eq!(
+            format_result(part("12"), Duration::from_millis(3)),
+            "not yet implemented"
+        );
+    }
+
+    #[test]
+    fn test_answer_render() {
+        assert_eq!(42_u64.render(), "42");
+        assert_eq!("CMZ".render(), "CMZ");
+        assert_eq!(("a".to_string(), 'b').render(), "a,b");
+        assert_eq!(((1, 2), 3).render(), "1,2,3");
+    }
+
+    #[test]
+    fn test_format_result_exec_time() {
+        // unsolved parts mustn't count towards the total time
+        let output = format!(
+            "{}\n{}",
+            format_result(Some("1"), Duration::from_millis(2)),
+            format_result(None::<u32>, Duration::from_millis(5))
+        );
+
+        assert_approx_eq!(parse_exec_time(&output), 2_f64);
+    }
+
+    #[test]
+    fn test_format_result_timings_round_trip() {
+        // solve! prints each part's time through format_result, and the README's timings are
+        // collected back out of that output, so every scale of time needs to survive the trip
+        for (elapsed, millis) in [
+            (Duration::from_nanos(500), 0_f64),
+            (Duration::from_micros(250), 0.25_f64),
+            (Duration::from_micros(12_300), 12.3_f64),
+            (Duration::from_millis(4_500), 4500_f64),
+        ] {
+            let output = format!("🎄 Part 1 🎄\n{}", format_result(Some(1), elapsed));
+            assert_approx_eq!(parse_exec_time(&output), millis);
+        }
+    }
+
+    #[test]
+    fn test_read_file() {
+        assert!(read_file("examples", 1).unwrap().contains('\n'));
+    }
+
+    #[test]
+    fn test_read_file_from() {
+        let path =
+            std::env::temp_dir().join(format!("aoc-read-file-from-{}.txt", std::process::id()));
+        fs::write(&path, "1000\n2000\n").unwrap();
+
+        let contents = read_file_from(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(contents.unwrap(), "1000\n2000\n");
+
+        let err = read_file_from(&path).unwrap_err();
+        assert_eq!(err.path, path);
+        assert_eq!(err.source.kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn test_read_example() {
+        assert_eq!(read_example(1).unwrap(), read_file("examples", 1).unwrap());
+
+        assert_eq!(
+            format!("{}", read_example(19).unwrap_err()),
+            "example for day 19 missing: create src/examples/19.txt"
+        );
+    }
+
+    #[test]
+    fn test_read_input_missing() {
+        assert_eq!(
+            format!("{}", read_input(99).unwrap_err()),
+            "input for day 99 missing: create src/inputs/99.txt"
+        );
+    }
+
+    #[test]
+    fn test_read_from() {
+        let input = read_from(io::Cursor::new("1000\n2000\n")).unwrap();
+        assert_eq!(input, "1000\n2000\n");
+    }
+
+    #[test]
+    fn test_read_input_or_stdin() {
+        let stdin = || io::Cursor::new("1000\n2000\n");
+        assert_eq!(read_input_or(99, stdin(), true).unwrap(), "1000\n2000\n");
+
+        // stdin is only read when something is piped in, and an empty pipe doesn't count
+        let missing = "input for day 99 missing: create src/inputs/99.txt";
+        assert_eq!(
+            read_input_or(99, stdin(), false).unwrap_err().to_string(),
+            missing
+        );
+        assert_eq!(
+            read_input_or(99, io::empty(), true)
+                .unwrap_err()
+                .to_string(),
+            missing
+        );
+    }
+
+    #[test]
+    fn test_read_file_missing() {
+        let err = read_file("examples", 99).unwrap_err();
+
+        assert_eq!(err.source.kind(), io::ErrorKind::NotFound);
+        assert!(format!("{}", err).starts_with("could not read src/examples/99.txt: "));
+    }
+
     #[test]
     fn test_parse_exec_time() {
         assert_approx_eq!(
@@ -6,6 +6,17 @@ use advent_of_code::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 use std::process::Command;
 
 fn main() {
+    let mut args = pico_args::Arguments::from_env();
+
+    if args.contains("--parallel") {
+        run_parallel();
+    } else {
+        run_each();
+    }
+}
+
+// Runs every day's binary in turn, so each day has the machine to itself while it's timed.
+fn run_each() {
     let total: f64 = (1..=25)
         .map(|day| {
             let day = format!("{:02}", day);
@@ -44,3 +55,29 @@ fn main() {
         ANSI_BOLD, ANSI_RESET, ANSI_ITALIC, total, ANSI_RESET
     );
 }
+
+// Runs every day at once within this process, which is quicker for a full year but makes for
+// noisier timings.
+fn run_parallel() {
+    let (runs, errors) = advent_of_code::solutions::run_all(advent_of_code::read_input);
+
+    for err in errors {
+        eprintln!("{}", err);
+    }
+
+    let report = runs
+        .iter()
+        .map(|run| run.to_string())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    println!("{}", report);
+    println!(
+        "{}Total:{} {}{:.2}ms{}",
+        ANSI_BOLD,
+        ANSI_RESET,
+        ANSI_ITALIC,
+        advent_of_code::parse_exec_time(&report),
+        ANSI_RESET
+    );
+}
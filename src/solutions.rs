@@ -0,0 +1,196 @@
+/*
+ * Gives the library access to every day's solution, so that they can be looked up and run by day
+ * and part number rather than only through each day's own binary.
+ *
+ * The days are still written as standalone binaries in `./bin`. They're pulled in here as modules
+ * too, which is why their `main` functions go unused. The modules are public so that integration
+ * tests can reach a day's other functions, such as reference implementations to check against.
+ *
+ * `cargo scaffold` adds new days to the list at the bottom of this file.
+ */
+
+use std::{
+    fmt::Display,
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{format_result, read_file, Answer, ReadFileError};
+
+// A uniform way to run either part of any day, whatever type its answers are.
+pub trait Solution: Sync {
+    fn part_one(&self, input: &str) -> Option<String>;
+    fn part_two(&self, input: &str) -> Option<String>;
+}
+
+macro_rules! days {
+    ($($day:literal => $module:ident $solution:ident $path:literal),* $(,)?) => {
+        $(
+            #[allow(dead_code)]
+            #[path = $path]
+            pub mod $module;
+
+            pub struct $solution;
+
+            impl Solution for $solution {
+                fn part_one(&self, input: &str) -> Option<String> {
+                    $module::part_one(input).map(|x| x.render())
+                }
+
+                fn part_two(&self, input: &str) -> Option<String> {
+                    $module::part_two(input).map(|x| x.render())
+                }
+            }
+        )*
+
+        // Every day's solution, in order, alongside its day number.
+        pub fn solutions() -> Vec<(u8, &'static dyn Solution)> {
+            vec![$(($day, &$solution)),*]
+        }
+
+        // Looks up the solver for a part of a day, with its answer rendered as a string so every day
+        // can share the same signature.
+        pub fn part(day: u8, part: u8) -> Option<Part> {
+            match (day, part) {
+                $(
+                    ($day, 1) => Some(|input| $module::part_one(input).map(|x| x.render())),
+                    ($day, 2) => Some(|input| $module::part_two(input).map(|x| x.render())),
+                )*
+                _ => None,
+            }
+        }
+    };
+}
+
+pub type Part = fn(&str) -> Option<String>;
+
+// The outcome of running one part of a day.
+#[derive(Debug)]
+pub struct Run {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub elapsed: Duration,
+}
+
+impl Display for Run {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Day {:02} part {}: {}",
+            self.day,
+            self.part,
+            format_result(self.answer.as_deref(), self.elapsed)
+        )
+    }
+}
+
+// Runs every part of every day at once, a thread per day, against the inputs given by read. The runs
+// come back in order of day and part. Days whose input can't be read are left out of the runs, and
+// their errors returned alongside instead.
+//
+// The days compete for the CPU while they run, so the timings are only a rough guide.
+pub fn run_all<F>(read: F) -> (Vec<Run>, Vec<ReadFileError>)
+where
+    F: Fn(u8) -> Result<String, ReadFileError> + Sync,
+{
+    let read = &read;
+
+    let results: Vec<Result<Vec<Run>, ReadFileError>> = thread::scope(|scope| {
+        let handles: Vec<_> = solutions()
+            .into_iter()
+            .map(|(day, solution)| {
+                scope.spawn(move || {
+                    let input = read(day)?;
+
+                    Ok([1, 2]
+                        .into_iter()
+                        .map(|part| {
+                            let timer = Instant::now();
+                            let answer = match part {
+                                1 => solution.part_one(&input),
+                                _ => solution.part_two(&input),
+                            };
+
+                            Run {
+                                day,
+                                part,
+                                answer,
+                                elapsed: timer.elapsed(),
+                            }
+                        })
+                        .collect())
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("a day panicked"))
+            .collect()
+    });
+
+    let (mut runs, mut errors) = (Vec::new(), Vec::new());
+
+    for result in results {
+        match result {
+            Ok(day) => runs.extend(day),
+            Err(err) => errors.push(err),
+        }
+    }
+
+    runs.sort_by_key(|run| (run.day, run.part));
+    (runs, errors)
+}
+
+// Runs a part of a day against its input the given number of times, returning the mean time taken by
+// a run. Reading the input isn't included. See the README for calling it from a binary.
+pub fn bench(day: u8, part: u8, iterations: usize) -> Duration {
+    assert!(iterations > 0, "need at least one iteration to benchmark");
+
+    let solver = self::part(day, part)
+        .unwrap_or_else(|| panic!("there is no solution for day {} part {}", day, part));
+    let input = read_file("inputs", day).unwrap_or_else(|err| panic!("{}", err));
+
+    bench_with(solver, &input, iterations)
+}
+
+// As bench, for any solver and input.
+pub fn bench_with(solver: Part, input: &str, iterations: usize) -> Duration {
+    assert!(iterations > 0, "need at least one iteration to benchmark");
+
+    let timer = Instant::now();
+
+    for _ in 0..iterations {
+        std::hint::black_box(solver(std::hint::black_box(input)));
+    }
+
+    timer.elapsed() / iterations as u32
+}
+
+days! {
+    1 => d01 Day01 "bin/01.rs",
+    2 => d02 Day02 "bin/02.rs",
+    3 => d03 Day03 "bin/03.rs",
+    4 => d04 Day04 "bin/04.rs",
+    5 => d05 Day05 "bin/05.rs",
+    6 => d06 Day06 "bin/06.rs",
+    7 => d07 Day07 "bin/07.rs",
+    8 => d08 Day08 "bin/08.rs",
+    9 => d09 Day09 "bin/09.rs",
+    10 => d10 Day10 "bin/10.rs",
+    11 => d11 Day11 "bin/11.rs",
+    12 => d12 Day12 "bin/12.rs",
+    13 => d13 Day13 "bin/13.rs",
+    14 => d14 Day14 "bin/14.rs",
+    15 => d15 Day15 "bin/15.rs",
+    16 => d16 Day16 "bin/16.rs",
+    17 => d17 Day17 "bin/17.rs",
+    18 => d18 Day18 "bin/18.rs",
+    20 => d20 Day20 "bin/20.rs",
+    21 => d21 Day21 "bin/21.rs",
+    22 => d22 Day22 "bin/22.rs",
+    23 => d23 Day23 "bin/23.rs",
+    24 => d24 Day24 "bin/24.rs",
+    25 => d25 Day25 "bin/25.rs",
+}
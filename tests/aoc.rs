@@ -0,0 +1,53 @@
+/*
+ * Runs the `aoc` binary, which dispatches to any day's solution, as it would be run from the
+ * command line.
+ */
+use std::{
+    io::Write,
+    process::{Command, Output, Stdio},
+};
+
+fn aoc(args: &[&str], input: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("starting aoc");
+
+    // There are no puzzle inputs checked in, so pipe the example in instead.
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn dispatches_to_day_1() {
+    let example = advent_of_code::read_example(1).unwrap();
+
+    for (part, answer) in [("1", "24000"), ("2", "45000")] {
+        let output = aoc(&["--day", "1", "--part", part], &example);
+        assert!(output.status.success(), "part {}: {:?}", part, output);
+
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        assert!(stdout.contains(&format!("Part {}", part)), "{}", stdout);
+        assert!(stdout.contains(answer), "part {}: {}", part, stdout);
+    }
+}
+
+#[test]
+fn reports_missing_days() {
+    let output = aoc(&["--day", "19", "--part", "1"], "");
+
+    assert!(!output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap().trim(),
+        "There is no solution for day 19 part 1."
+    );
+}
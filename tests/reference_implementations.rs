@@ -0,0 +1,199 @@
+/*
+ * Checks that days with both a slow, obviously-correct reference implementation and a faster one
+ * agree on randomly generated puzzles, so that the fast ones can be refactored with some
+ * confidence.
+ *
+ * The inputs are generated from fixed seeds so that any failure can be reproduced.
+ */
+use advent_of_code::solutions::{d12, d17, d18, d24};
+
+// A small xorshift generator; it's plenty random enough to make up puzzles.
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        // xorshift gets stuck on zero, and small seeds take a while to get going
+        Rng(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    // A number from the inclusive range.
+    fn range(&mut self, low: usize, high: usize) -> usize {
+        low + (self.next() % (high - low + 1) as u64) as usize
+    }
+
+    fn pick<T: Copy>(&mut self, items: &[T]) -> T {
+        items[self.range(0, items.len() - 1)]
+    }
+}
+
+const SEEDS: u64 = 50;
+
+// A heightmap with elevations drawn from a narrow range, so that there is often, but not always, a
+// path from start to end.
+fn random_heightmap(rng: &mut Rng) -> String {
+    let (width, height) = (rng.range(2, 12), rng.range(2, 8));
+    let mut cells: Vec<char> = (0..width * height)
+        .map(|_| rng.pick(&['a', 'b', 'c', 'd']))
+        .collect();
+
+    let start = rng.range(0, cells.len() - 1);
+    let end = (start + rng.range(1, cells.len() - 1)) % cells.len();
+    cells[start] = 'S';
+    cells[end] = 'E';
+
+    cells
+        .chunks(width)
+        .map(|row| row.iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// Dijkstra is A* without a heuristic, so part one is also checked against a breadth first search
+// which doesn't share any of that code. Part two already searches breadth first.
+#[test]
+fn day_12_astar_matches_bfs() {
+    for seed in 0..SEEDS {
+        let input = random_heightmap(&mut Rng::new(seed));
+
+        assert_eq!(
+            d12::part_one(&input),
+            d12::part_one_by_bfs(&input),
+            "part one, seed {}:\n{}",
+            seed,
+            input
+        );
+        assert_eq!(
+            d12::part_one_by_dijkstra(&input),
+            d12::part_one_by_bfs(&input),
+            "part one, seed {}:\n{}",
+            seed,
+            input
+        );
+        assert_eq!(
+            d12::part_two(&input),
+            d12::part_two_by_dijkstra(&input),
+            "part two, seed {}:\n{}",
+            seed,
+            input
+        );
+    }
+}
+
+#[test]
+fn day_17_cycle_matches_simulation() {
+    // Simulating is slow, so try fewer towers.
+    for seed in 0..SEEDS / 5 {
+        let mut rng = Rng::new(seed);
+
+        // Very short jet patterns can leave a column that no rock ever reaches, which the cycle
+        // finder doesn't support, so stick to something more like the real puzzle.
+        let input: String = (0..rng.range(100, 300))
+            .map(|_| rng.pick(&['<', '>']))
+            .collect();
+
+        // Short of the cycle, and then a cycle or so into it.
+        for rocks in [15, 2022, 5000] {
+            assert_eq!(
+                d17::tower_height_by_cycle(&input, rocks as i64),
+                d17::tower_height_by_simulation(&input, rocks),
+                "{} rocks, seed {}: {}",
+                rocks,
+                seed,
+                input
+            );
+        }
+    }
+}
+
+#[test]
+fn day_18_planes_match_neighbours() {
+    for seed in 0..SEEDS {
+        let mut rng = Rng::new(seed);
+
+        // Dense enough in a small space that there are plenty of air pockets.
+        let mut cubes: Vec<(usize, usize, usize)> = (0..rng.range(1, 60))
+            .map(|_| (rng.range(0, 5), rng.range(0, 5), rng.range(0, 5)))
+            .collect();
+        cubes.sort();
+        cubes.dedup();
+
+        let input = cubes
+            .iter()
+            .map(|(x, y, z)| format!("{},{},{}", x, y, z))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        assert_eq!(
+            d18::part_one(&input),
+            Some(d18::surface_area_by_neighbours(&input)),
+            "part one, seed {}:\n{}",
+            seed,
+            input
+        );
+        assert_eq!(
+            d18::part_two(&input),
+            Some(d18::exterior_surface_area_by_flood_fill(&input)),
+            "part two, seed {}:\n{}",
+            seed,
+            input
+        );
+    }
+}
+
+// A valley with blizzards scattered through it. Vertical blizzards are kept out of the portals'
+// columns, as they are in the real puzzle; the solutions assume they never blow into a portal.
+fn random_valley(rng: &mut Rng) -> String {
+    let (width, height) = (rng.range(1, 8), rng.range(1, 6));
+
+    let mut lines = vec![format!("#.{}", "#".repeat(width))];
+
+    for _ in 0..height {
+        let row: String = (0..width)
+            .map(|col| {
+                let choices: &[char] = if col == 0 || col == width - 1 {
+                    &['.', '.', '.', '<', '>']
+                } else {
+                    &['.', '.', '.', '<', '>', '^', 'v']
+                };
+                rng.pick(choices)
+            })
+            .collect();
+        lines.push(format!("#{}#", row));
+    }
+
+    lines.push(format!("{}.#", "#".repeat(width)));
+    lines.join("\n")
+}
+
+#[test]
+fn day_24_bfs_matches_reachability() {
+    let mut crossed = 0;
+
+    for seed in 0..SEEDS {
+        let input = random_valley(&mut Rng::new(seed));
+        let expected = d24::total_time(&input, 3);
+
+        assert_eq!(
+            d24::total_time_by_reachability(&input, 3),
+            expected,
+            "seed {}:\n{}",
+            seed,
+            input
+        );
+
+        if expected.is_some() {
+            crossed += 1;
+        }
+    }
+
+    // Both sorts of valley need to turn up for the check to be worth anything.
+    assert!(crossed > 0, "no random valley could be crossed");
+    assert!(crossed < SEEDS, "every random valley could be crossed");
+}
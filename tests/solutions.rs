@@ -0,0 +1,136 @@
+/*
+ * Checks the registry of every day's solution in advent_of_code::solutions. The days' own unit
+ * tests are run from their binaries, so the registry is tested from here, where the library is
+ * built with the days in it.
+ */
+use advent_of_code::{
+    bench, read_example, read_file,
+    solutions::{self, run_all, solutions},
+};
+
+// Day 10 draws its answer to part two.
+const DAY_10_PART_TWO: &str = "##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######.....";
+
+// Day 15 only searches the example's smaller area under cfg!(test), which is set for its own tests
+// but not for the library these tests run against, so its answers can't be checked from here.
+const SMALLER_IN_TESTS: u8 = 15;
+
+// The answers to each day's example, as its own tests expect.
+const EXAMPLES: [(u8, Option<&str>, Option<&str>); 24] = [
+    (1, Some("24000"), Some("45000")),
+    (2, Some("15"), Some("12")),
+    (3, Some("157"), Some("70")),
+    (4, Some("2"), Some("4")),
+    (5, Some("CMZ"), Some("MCD")),
+    (6, Some("10"), Some("29")),
+    (7, Some("95437"), Some("24933642")),
+    (8, Some("21"), Some("8")),
+    (9, Some("88"), Some("36")),
+    (10, Some("13140"), Some(DAY_10_PART_TWO)),
+    (11, Some("10605"), Some("2713310158")),
+    (12, Some("31"), Some("29")),
+    (13, Some("13"), Some("140")),
+    (14, Some("24"), Some("93")),
+    (15, Some("26"), Some("56000011")),
+    (16, Some("1651"), Some("1707")),
+    (17, Some("3068"), Some("1514285714288")),
+    (18, Some("64"), Some("58")),
+    (20, Some("3"), Some("1623178306")),
+    (21, Some("152"), Some("301")),
+    (22, Some("6032"), Some("5031")),
+    (23, Some("110"), Some("20")),
+    (24, Some("18"), Some("54")),
+    (25, Some("2=-1=0"), None),
+];
+
+#[test]
+fn test_solutions_examples() {
+    let solutions = solutions();
+    assert_eq!(solutions.len(), EXAMPLES.len());
+
+    for ((day, solution), (example_day, part_one, part_two)) in solutions.iter().zip(EXAMPLES) {
+        assert_eq!(*day, example_day);
+
+        if *day == SMALLER_IN_TESTS {
+            continue;
+        }
+
+        let input = read_example(*day).unwrap();
+        assert_eq!(
+            solution.part_one(&input).as_deref(),
+            part_one,
+            "day {}",
+            day
+        );
+        assert_eq!(
+            solution.part_two(&input).as_deref(),
+            part_two,
+            "day {}",
+            day
+        );
+    }
+}
+
+#[test]
+fn test_run_all_examples() {
+    let (runs, errors) = run_all(read_example);
+
+    assert!(errors.is_empty(), "{:?}", errors);
+    assert_eq!(runs.len(), EXAMPLES.len() * 2);
+
+    for (runs, (day, part_one, part_two)) in runs.chunks(2).zip(EXAMPLES) {
+        assert_eq!((runs[0].day, runs[0].part), (day, 1));
+        assert_eq!((runs[1].day, runs[1].part), (day, 2));
+
+        if day == SMALLER_IN_TESTS {
+            continue;
+        }
+
+        assert_eq!(runs[0].answer.as_deref(), part_one, "day {}", day);
+        assert_eq!(runs[1].answer.as_deref(), part_two, "day {}", day);
+    }
+}
+
+#[test]
+fn test_run_all_missing_inputs() {
+    // none of the puzzle inputs are checked in
+    let (runs, errors) = run_all(|day| {
+        if day == 1 {
+            read_example(day)
+        } else {
+            read_file("missing", day)
+        }
+    });
+
+    assert_eq!(runs.len(), 2);
+    assert_eq!(errors.len(), EXAMPLES.len() - 1);
+    assert!(runs[0].to_string().starts_with("Day 01 part 1: 24000 "));
+}
+
+#[test]
+fn test_solutions_part() {
+    let input = read_file("examples", 1).unwrap();
+
+    assert_eq!(
+        solutions::part(1, 1).unwrap()(&input),
+        Some("24000".to_string())
+    );
+    assert_eq!(
+        solutions::part(25, 1).unwrap()(&read_file("examples", 25).unwrap()),
+        Some("2=-1=0".to_string())
+    );
+
+    assert!(solutions::part(19, 1).is_none());
+    assert!(solutions::part(1, 3).is_none());
+}
+
+#[test]
+#[should_panic(expected = "there is no solution for day 19 part 1")]
+fn test_bench_unknown_day() {
+    bench(19, 1, 1);
+}